@@ -0,0 +1,38 @@
+//! Day 6 part two, sequential against parallel, on the cached input:
+//! `cargo bench -p aoc --features parallel`.
+
+use std::process::ExitCode;
+
+use aoc::bench::{self, Nanos};
+use aoc::days::day_6::{self, LoopFinder, Map};
+use aoc::input;
+
+const ITERATIONS: usize = 20;
+
+fn main() -> ExitCode {
+    let data: String = match input::default_provider().input(6) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let map: Map = match day_6::load_data(&data) {
+        Ok(map) => map,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let finder: LoopFinder = LoopFinder::new(&map).with_jumps();
+    assert_eq!(finder.obstructions(), finder.par_obstructions(), "both modes must find the same obstructions");
+
+    println!("{:<12}  {:>10}  {:>10}  {:>10}", "mode", "min", "median", "mean");
+    for (mode, stats) in [
+        ("sequential", bench::time(ITERATIONS, || finder.obstructions())),
+        ("parallel", bench::time(ITERATIONS, || finder.par_obstructions())),
+    ] {
+        println!("{:<12}  {:>10}  {:>10}  {:>10}", mode, Nanos(stats.min_ns), Nanos(stats.median_ns), Nanos(stats.mean_ns));
+    }
+    ExitCode::SUCCESS
+}
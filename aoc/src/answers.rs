@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::solution::{Answer, Part};
+
+#[derive(Debug)]
+pub enum AnswerDbError {
+    Io(PathBuf, io::Error),
+    Invalid(PathBuf, String),
+}
+
+impl fmt::Display for AnswerDbError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswerDbError::Io(path, err) => write!(f, "cannot access {}: {}", path.display(), err),
+            AnswerDbError::Invalid(path, message) => write!(f, "{}: {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for AnswerDbError {}
+
+/// How a fresh answer compares to the accepted one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { .. } => write!(f, "fail"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// The accepted answers, stored in `answers.toml` as one table per day keyed by part:
+///
+/// ```toml
+/// [6]
+/// 1 = 5145
+/// 2 = 1523
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AnswerDb {
+    answers: BTreeMap<u8, BTreeMap<Part, String>>,
+}
+
+impl AnswerDb {
+    /// `answers.toml` at the root of the workspace.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().join("answers.toml")
+    }
+
+    /// Read the database at `path`, which is empty while the file doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<AnswerDb, AnswerDbError> {
+        let path: &Path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(data) => AnswerDb::parse(&data).map_err(|message| AnswerDbError::Invalid(path.to_path_buf(), message)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(AnswerDb::default()),
+            Err(err) => Err(AnswerDbError::Io(path.to_path_buf(), err)),
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<AnswerDb, String> {
+        let table: toml::Table = data.parse().map_err(|err: toml::de::Error| err.message().to_string())?;
+        let mut db: AnswerDb = AnswerDb::default();
+        for (day, parts) in table {
+            let day_number: u8 = day.parse().map_err(|_| format!("`{}` is not a day", day))?;
+            let toml::Value::Table(parts) = parts else {
+                return Err(format!("day {} should be a table of parts", day));
+            };
+            for (part, value) in parts {
+                let part: Part = part
+                    .parse()
+                    .ok()
+                    .and_then(Part::from_number)
+                    .ok_or_else(|| format!("`{}` is not a part of day {}", part, day))?;
+                // ? Small numbers are written bare, the rest (text or past i64) as strings
+                let expected: String = match value {
+                    toml::Value::Integer(n) => n.to_string(),
+                    toml::Value::String(s) => s,
+                    other => return Err(format!("unexpected {} for day {} {}", other.type_str(), day, part)),
+                };
+                db.answers.entry(day_number).or_default().insert(part, expected);
+            }
+        }
+        Ok(db)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AnswerDbError> {
+        let path: &Path = path.as_ref();
+        std::fs::write(path, self.to_string()).map_err(|err| AnswerDbError::Io(path.to_path_buf(), err))
+    }
+
+    pub fn expected(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&day)?.get(&part).map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer) {
+        self.answers.entry(day).or_default().insert(part, answer.to_string());
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+        if let Answer::Error(_) = answer {
+            return Verdict::Fail { expected: self.expected(day, part).unwrap_or("-").to_string() };
+        }
+        match self.expected(day, part) {
+            None => Verdict::Unknown,
+            Some(expected) if *answer != Answer::Unsolved && expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected: expected.to_string() },
+        }
+    }
+}
+
+impl fmt::Display for AnswerDb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Accepted answers: day -> part -> value, checked by `aoc verify`.")?;
+        for (day, parts) in &self.answers {
+            writeln!(f, "\n[{}]", day)?;
+            for (part, expected) in parts {
+                match expected.parse::<i64>() {
+                    Ok(n) => writeln!(f, "{} = {}", part.number(), n)?,
+                    Err(_) => writeln!(f, "{} = {}", part.number(), toml::Value::String(expected.clone()))?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_numbers_and_text() {
+        let mut db: AnswerDb = AnswerDb::default();
+        db.record(13, Part::Two, &Answer::Signed(79352015273424));
+        db.record(2, Part::One, &Answer::Unsigned(u128::MAX));
+        db.record(2, Part::Two, &Answer::Text("a \"quoted\" answer".to_string()));
+
+        let reloaded: AnswerDb = AnswerDb::parse(&db.to_string()).unwrap();
+        assert_eq!(reloaded.expected(13, Part::Two), Some("79352015273424"));
+        assert_eq!(reloaded.expected(2, Part::One), Some(u128::MAX.to_string().as_str()));
+        assert_eq!(reloaded.expected(2, Part::Two), Some("a \"quoted\" answer"));
+        assert_eq!(reloaded.expected(13, Part::One), None);
+    }
+
+    #[test]
+    fn checks_against_the_recorded_answer() {
+        let db: AnswerDb = AnswerDb::parse("[6]\n1 = 41\n").unwrap();
+        assert_eq!(db.check(6, Part::One, &Answer::Unsigned(41)), Verdict::Pass);
+        assert_eq!(db.check(6, Part::One, &Answer::Unsigned(40)), Verdict::Fail { expected: "41".to_string() });
+        assert_eq!(db.check(6, Part::Two, &Answer::Unsigned(6)), Verdict::Unknown);
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert!(AnswerDb::parse("[six]\n1 = 41\n").is_err());
+        assert!(AnswerDb::parse("[6]\n3 = 41\n").is_err());
+        assert!(AnswerDb::parse("[6]\n1 = 4.1\n").is_err());
+    }
+}
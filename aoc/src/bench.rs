@@ -0,0 +1,127 @@
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+/// Summary of repeated timings of one phase, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut nanos: Vec<u64> = samples.iter().map(|sample| sample.as_nanos() as u64).collect();
+        nanos.sort_unstable();
+        if nanos.is_empty() {
+            return Stats { min_ns: 0, median_ns: 0, mean_ns: 0, stddev_ns: 0 };
+        }
+        let mid: usize = nanos.len() / 2;
+        let median: u64 = if nanos.len().is_multiple_of(2) { (nanos[mid - 1] + nanos[mid]) / 2 } else { nanos[mid] };
+        let mean: f64 = nanos.iter().sum::<u64>() as f64 / nanos.len() as f64;
+        let variance: f64 = nanos.iter().map(|&n| (n as f64 - mean).powi(2)).sum::<f64>() / nanos.len() as f64;
+        Stats {
+            min_ns: nanos[0],
+            median_ns: median,
+            mean_ns: mean.round() as u64,
+            stddev_ns: variance.sqrt().round() as u64,
+        }
+    }
+}
+
+/// Timings of every phase of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DayTimings {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+}
+
+/// Time parsing and both parts of `S` separately, `iterations` times each after one untimed warm-up run.
+pub fn measure<S: Solution>(input: &str, iterations: usize) -> Result<DayTimings, ParseError> {
+    // ? The warm-up also reports parse errors once, before anything is timed
+    let parsed: S::Input = S::parse(input)?;
+    black_box(S::part_one(&parsed).into());
+    black_box(S::part_two(&parsed).into());
+
+    let mut parse: Vec<Duration> = Vec::with_capacity(iterations);
+    let mut part_one: Vec<Duration> = Vec::with_capacity(iterations);
+    let mut part_two: Vec<Duration> = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start: Instant = Instant::now();
+        let parsed: S::Input = black_box(S::parse(black_box(input))?);
+        parse.push(start.elapsed());
+
+        let start: Instant = Instant::now();
+        black_box(S::part_one(black_box(&parsed)));
+        part_one.push(start.elapsed());
+
+        let start: Instant = Instant::now();
+        black_box(S::part_two(black_box(&parsed)));
+        part_two.push(start.elapsed());
+    }
+
+    Ok(DayTimings {
+        day: S::DAY,
+        iterations,
+        parse: Stats::from_samples(&parse),
+        part_one: Stats::from_samples(&part_one),
+        part_two: Stats::from_samples(&part_two),
+    })
+}
+
+/// Time `f` alone, `iterations` times after one untimed warm-up run.
+pub fn time<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    black_box(f());
+    let samples: Vec<Duration> = (0..iterations)
+        .map(|_| {
+            let start: Instant = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&samples)
+}
+
+/// A duration in nanoseconds, printed with the unit that keeps it readable.
+pub struct Nanos(pub u64);
+
+impl fmt::Display for Nanos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text: String = match self.0 {
+            n if n < 1_000 => format!("{} ns", n),
+            n if n < 1_000_000 => format!("{:.2} µs", n as f64 / 1e3),
+            n if n < 1_000_000_000 => format!("{:.2} ms", n as f64 / 1e6),
+            n => format!("{:.2} s", n as f64 / 1e9),
+        };
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_samples() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].into_iter().map(Duration::from_nanos).collect();
+        let stats: Stats = Stats::from_samples(&samples);
+        assert_eq!(stats, Stats { min_ns: 1, median_ns: 2, mean_ns: 3, stddev_ns: 1 });
+    }
+
+    #[test]
+    fn picks_a_readable_unit() {
+        assert_eq!(Nanos(999).to_string(), "999 ns");
+        assert_eq!(Nanos(1_500).to_string(), "1.50 µs");
+        assert_eq!(Nanos(2_250_000_000).to_string(), "2.25 s");
+    }
+}
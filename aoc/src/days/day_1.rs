@@ -0,0 +1,366 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::error::{parse_token, ParseError};
+use crate::solution::Solution;
+
+/// A number the lists can hold.
+pub trait Value: FromStr + Ord + Copy + fmt::Display {
+    /// How far apart two values are, wide enough for any pair of them.
+    fn distance(self, other: Self) -> u128;
+    /// The value as an `i128` for similarity scores, `None` for a `u128` past `i128::MAX`.
+    fn widen(self) -> Option<i128>;
+}
+
+macro_rules! impl_value {
+    ($($t:ty),*) => {
+        $(impl Value for $t {
+            fn distance(self, other: $t) -> u128 {
+                self.abs_diff(other) as u128
+            }
+            fn widen(self) -> Option<i128> {
+                i128::try_from(self).ok()
+            }
+        })*
+    };
+}
+impl_value!(i32, i64, i128, u32, u64, u128);
+
+/// The lists side by side, one `Vec` per column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columns<T> {
+    columns: Vec<Vec<T>>,
+}
+
+impl<T: Value> Columns<T> {
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn column(&self, idx: usize) -> Option<&[T]> {
+        self.columns.get(idx).map(Vec::as_slice)
+    }
+
+    /// Pair the smallest values of columns `a` and `b`, then the second smallest and so on,
+    /// and sum how far apart each pair is. `None` if either column doesn't exist, or if the sum
+    /// doesn't fit in a `u128`, which only `u128` and `i128` columns can reach.
+    pub fn distance(&self, a: usize, b: usize) -> Option<u128> {
+        let mut left: Vec<T> = self.column(a)?.to_vec();
+        let mut right: Vec<T> = self.column(b)?.to_vec();
+        left.sort_unstable();
+        right.sort_unstable();
+        left.into_iter().zip(right).try_fold(0u128, |total, (l, r)| total.checked_add(l.distance(r)))
+    }
+}
+
+/// Read whitespace separated columns from `reader` one line at a time, so large inputs never sit
+/// in memory as text. `width` fixes the number of columns, otherwise the first line decides it.
+pub fn read_columns<T: Value>(mut reader: impl BufRead, width: Option<usize>) -> Result<Columns<T>, ParseError> {
+    let mut columns: Vec<Vec<T>> = (0..width.unwrap_or(0)).map(|_| Vec::new()).collect();
+    let mut width: Option<usize> = width;
+    let mut buffer: String = String::new();
+    for idx in 0.. {
+        buffer.clear();
+        let read: usize = reader
+            .read_line(&mut buffer)
+            .map_err(|err| ParseError::new(idx + 1, 1, "", format!("cannot read the input: {}", err)))?;
+        if read == 0 {
+            break;
+        }
+        let line: &str = buffer.trim_end_matches(['\n', '\r']);
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let count: usize = line.split_whitespace().count();
+        let width: usize = *width.get_or_insert_with(|| {
+            columns = (0..count).map(|_| Vec::new()).collect();
+            count
+        });
+        if count < width {
+            return Err(ParseError::at(idx, line, &line[line.len()..], format!("expected {} columns", width)));
+        }
+        if let Some(extra) = line.split_whitespace().nth(width) {
+            return Err(ParseError::at(idx, line, extra, format!("expected {} columns", width)));
+        }
+        for (column, token) in columns.iter_mut().zip(line.split_whitespace()) {
+            column.push(parse_token(idx, line, token)?);
+        }
+    }
+    Ok(Columns { columns })
+}
+
+pub fn read_input(data: &str) -> Result<Columns<i64>, ParseError> {
+    read_columns(data.as_bytes(), Some(2))
+}
+
+pub fn part_one(columns: &Columns<i64>) -> u128 {
+    // ? Each i64 pair is under 2^64 apart, so fewer than 2^64 of them cannot overflow the total
+    columns.distance(0, 1).expect("read_input always reads two columns")
+}
+
+/// How many times each value appears in a list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrequencyTable<T> {
+    counts: BTreeMap<T, u64>,
+}
+
+impl<T: Value> FrequencyTable<T> {
+    pub fn new(values: &[T]) -> FrequencyTable<T> {
+        let mut counts: BTreeMap<T, u64> = BTreeMap::new();
+        for value in values.iter() {
+            *counts.entry(*value).or_insert(0) += 1;
+        }
+        FrequencyTable { counts }
+    }
+
+    pub fn count(&self, value: &T) -> u64 {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// Every distinct value with how many times it appears, smallest value first.
+    pub fn histogram(&self) -> impl Iterator<Item = (T, u64)> + '_ {
+        self.counts.iter().map(|(value, count)| (*value, *count))
+    }
+
+    /// How much each distinct value of this list adds to its similarity score with `other`, or the
+    /// first value that takes a contribution or the total past `i128`.
+    pub fn similarity(&self, other: &FrequencyTable<T>) -> Result<Similarity<T>, ScoreOverflow<T>> {
+        let mut contributions: Vec<Contribution<T>> = Vec::with_capacity(self.counts.len());
+        let mut total: i128 = 0;
+        for (value, left) in self.histogram() {
+            let right: u64 = other.count(&value);
+            // ? A value missing from the right list adds nothing, however large it is
+            let score: Option<i128> = if right == 0 {
+                Some(0)
+            } else {
+                value.widen().and_then(|value| value.checked_mul(left as i128)?.checked_mul(right as i128))
+            };
+            let Some(score) = score else {
+                return Err(ScoreOverflow { value });
+            };
+            total = total.checked_add(score).ok_or(ScoreOverflow { value })?;
+            contributions.push(Contribution { value, left, right, score });
+        }
+        Ok(Similarity { contributions, total })
+    }
+}
+
+/// What one distinct value adds to the similarity score: itself, times its count in the left list,
+/// times its count in the right one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contribution<T> {
+    pub value: T,
+    pub left: u64,
+    pub right: u64,
+    pub score: i128,
+}
+
+/// A similarity score that doesn't fit in an `i128`, with the value that took it past.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreOverflow<T> {
+    pub value: T,
+}
+
+impl<T: fmt::Display> fmt::Display for ScoreOverflow<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the similarity score overflows an i128 at value {}", self.value)
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> std::error::Error for ScoreOverflow<T> {}
+
+/// The similarity score of two lists, broken down by value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Similarity<T> {
+    contributions: Vec<Contribution<T>>,
+    total: i128,
+}
+
+impl<T: Value> Similarity<T> {
+    pub fn score(&self) -> i128 {
+        self.total
+    }
+
+    /// One entry per distinct value of the left list, smallest value first.
+    pub fn breakdown(&self) -> &[Contribution<T>] {
+        &self.contributions
+    }
+
+    /// The `k` values that move the score the most, largest first.
+    pub fn top(&self, k: usize) -> Vec<Contribution<T>> {
+        let mut top: Vec<Contribution<T>> = self.contributions.iter().filter(|c| c.score != 0).copied().collect();
+        top.sort_by(|a, b| b.score.abs().cmp(&a.score.abs()).then(a.value.cmp(&b.value)));
+        top.truncate(k);
+        top
+    }
+}
+
+/// Explain the similarity score of the first two columns: the `top` contributors, optionally every
+/// value, and the share of the score each one accounts for.
+pub fn report(columns: &Columns<i64>, top: usize, breakdown: bool) -> Result<String, ScoreOverflow<i64>> {
+    let left: FrequencyTable<i64> = FrequencyTable::new(columns.column(0).unwrap());
+    let right: FrequencyTable<i64> = FrequencyTable::new(columns.column(1).unwrap());
+    let similarity: Similarity<i64> = left.similarity(&right)?;
+    let score: i128 = similarity.score();
+    let matching: usize = similarity.breakdown().iter().filter(|c| c.right > 0).count();
+
+    let mut out: String = String::new();
+    writeln!(out, "similarity score: {}", score).unwrap();
+    writeln!(out, "{} of {} distinct left values appear in the right list", matching, similarity.breakdown().len()).unwrap();
+    let table = |out: &mut String, title: &str, rows: &[Contribution<i64>]| {
+        writeln!(out, "\n{}:", title).unwrap();
+        writeln!(out, "{:>12}  {:>5}  {:>5}  {:>14}  {:>6}", "value", "left", "right", "score", "share").unwrap();
+        for c in rows {
+            let share: f64 = if score == 0 { 0.0 } else { c.score as f64 * 100.0 / score as f64 };
+            writeln!(out, "{:>12}  {:>5}  {:>5}  {:>14}  {:>5.1}%", c.value, c.left, c.right, c.score, share).unwrap();
+        }
+    };
+    table(&mut out, &format!("top {} contributors", top), &similarity.top(top));
+    if breakdown {
+        table(&mut out, "every value", similarity.breakdown());
+    }
+    Ok(out)
+}
+
+/// Both lists as a `value,left,right` CSV histogram, smallest value first.
+pub fn histogram_csv(columns: &Columns<i64>) -> String {
+    let left: FrequencyTable<i64> = FrequencyTable::new(columns.column(0).unwrap());
+    let right: FrequencyTable<i64> = FrequencyTable::new(columns.column(1).unwrap());
+    let mut values: Vec<i64> = left.histogram().chain(right.histogram()).map(|(value, _)| value).collect();
+    values.sort_unstable();
+    values.dedup();
+
+    let mut out: String = String::from("value,left,right\n");
+    for value in values {
+        writeln!(out, "{},{},{}", value, left.count(&value), right.count(&value)).unwrap();
+    }
+    out
+}
+
+pub fn part_two(columns: &Columns<i64>) -> Result<i128, ScoreOverflow<i64>> {
+    let left: FrequencyTable<i64> = FrequencyTable::new(columns.column(0).unwrap());
+    let right: FrequencyTable<i64> = FrequencyTable::new(columns.column(1).unwrap());
+    Ok(left.similarity(&right)?.score())
+}
+
+pub struct Day1;
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Columns<i64>;
+    type PartOne = u128;
+    type PartTwo = Result<i128, ScoreOverflow<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, ParseError> {
+        read_columns(reader, Some(2))
+    }
+    fn part_one(columns: &Self::Input) -> u128 {
+        part_one(columns)
+    }
+    fn part_two(columns: &Self::Input) -> Result<i128, ScoreOverflow<i64>> {
+        part_two(columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::solution::{Answer, Part};
+
+    #[test]
+    fn distance_between_any_two_columns() {
+        let columns: Columns<i64> = read_columns("3 4 -1\n4 3 -1\n2 5 7\n".as_bytes(), None).unwrap();
+        assert_eq!(columns.width(), 3);
+        assert_eq!(columns.column(2), Some(&[-1, -1, 7][..]));
+        assert_eq!(columns.distance(0, 1), Some(3));
+        assert_eq!(columns.distance(0, 2), Some(10));
+        assert_eq!(columns.distance(0, 3), None);
+    }
+
+    #[test]
+    fn holds_values_past_i64() {
+        let columns: Columns<u128> = read_columns("18446744073709551616 0\n".as_bytes(), None).unwrap();
+        assert_eq!(columns.distance(0, 1), Some(1 << 64));
+        let columns: Columns<u64> = read_columns("18446744073709551615 0\n".as_bytes(), Some(2)).unwrap();
+        assert_eq!(columns.distance(1, 0), Some(u64::MAX as u128));
+    }
+
+    #[test]
+    fn distance_overflowing_u128() {
+        let max: u128 = u128::MAX;
+        let columns: Columns<u128> = read_columns(format!("{} 0\n", max).as_bytes(), None).unwrap();
+        assert_eq!(columns.distance(0, 1), Some(max));
+        let columns: Columns<u128> = read_columns(format!("{} 0\n1 0\n", max).as_bytes(), None).unwrap();
+        assert_eq!(columns.distance(0, 1), None);
+        let columns: Columns<i128> = read_columns(format!("{} {}\n", i128::MIN, i128::MAX).as_bytes(), None).unwrap();
+        assert_eq!(columns.distance(0, 1), Some(max));
+    }
+
+    #[test]
+    fn similarity_by_value() {
+        let columns: Columns<i64> = read_input("3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n").unwrap();
+        let left: FrequencyTable<i64> = FrequencyTable::new(columns.column(0).unwrap());
+        let right: FrequencyTable<i64> = FrequencyTable::new(columns.column(1).unwrap());
+        assert_eq!(left.histogram().collect::<Vec<_>>(), vec![(1, 1), (2, 1), (3, 3), (4, 1)]);
+
+        let similarity: Similarity<i64> = left.similarity(&right).unwrap();
+        assert_eq!(similarity.score(), 31);
+        assert_eq!(similarity.breakdown().len(), 4);
+        assert_eq!(
+            similarity.top(5),
+            vec![
+                Contribution { value: 3, left: 3, right: 3, score: 27 },
+                Contribution { value: 4, left: 1, right: 1, score: 4 },
+            ]
+        );
+        assert!(histogram_csv(&columns).starts_with("value,left,right\n1,1,0\n2,1,0\n3,3,3\n"));
+    }
+
+    #[test]
+    fn similarity_overflowing_i128() {
+        let big: u128 = i128::MAX as u128 + 1;
+        let columns: Columns<u128> = read_columns(format!("{} 1\n1 1\n", big).as_bytes(), None).unwrap();
+        let left: FrequencyTable<u128> = FrequencyTable::new(columns.column(0).unwrap());
+        let right: FrequencyTable<u128> = FrequencyTable::new(columns.column(1).unwrap());
+        // ? Unmatched, the value past i128 adds nothing; matched, it cannot be scored
+        assert_eq!(left.similarity(&right).unwrap().score(), 2);
+        assert_eq!(right.similarity(&left), Ok(Similarity { contributions: vec![Contribution { value: 1, left: 2, right: 1, score: 2 }], total: 2 }));
+        assert_eq!(left.similarity(&left), Err(ScoreOverflow { value: big }));
+
+        let columns: Columns<i64> = read_input(&format!("{} {}\n", i64::MAX, i64::MAX).repeat(3)).unwrap();
+        let left: FrequencyTable<i64> = FrequencyTable::new(columns.column(0).unwrap());
+        let right: FrequencyTable<i64> = FrequencyTable::new(columns.column(1).unwrap());
+        assert_eq!(left.similarity(&right).unwrap().score(), i64::MAX as i128 * 9);
+        let values: Vec<i128> = vec![i128::MAX / 2, 1, i128::MAX / 2 + 1];
+        let table: FrequencyTable<i128> = FrequencyTable::new(&values);
+        assert_eq!(table.similarity(&table), Err(ScoreOverflow { value: i128::MAX / 2 + 1 }));
+        assert_eq!(part_two(&columns), Ok(i64::MAX as i128 * 9));
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let missing: ParseError = read_columns::<i64>("1 2\n3\n".as_bytes(), None).unwrap_err();
+        assert_eq!((missing.line, missing.column, missing.message.as_str()), (2, 2, "expected 2 columns"));
+        let extra: ParseError = read_columns::<i64>("1 2 9\n".as_bytes(), Some(2)).unwrap_err();
+        assert_eq!((extra.line, extra.column, extra.text.as_str()), (1, 5, "9"));
+        let garbage: ParseError = read_columns::<u64>("1 -2\n".as_bytes(), None).unwrap_err();
+        assert_eq!((garbage.line, garbage.column, garbage.text.as_str()), (1, 3, "-2"));
+    }
+
+    #[test]
+    fn streams_the_same_answers() {
+        let data: &str = "3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n";
+        let solved: Vec<(Part, Answer, Duration)> = Day1::solve_parts(&mut data.as_bytes(), &Part::ALL).unwrap();
+        assert_eq!(solved.len(), 2);
+        for (part, answer, _) in solved {
+            assert_eq!(answer, Day1::solve(data, part).unwrap());
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use crate::error::ParseError;
+use crate::geometry::Pos;
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+pub type Map = Grid<u32>;
+
+pub fn load_data(data: &str) -> Result<Map, ParseError> {
+    Grid::parse(data, |c| match c {
+        '0'..='9' => c.to_digit(10),
+        '.' => Some(15),
+        _ => None,
+    })
+}
+
+pub fn part_one(map: &Map) -> u32 {
+    let mut reachable_goals: HashMap<Pos, Vec<Pos>> = HashMap::new();
+
+    fn traverse(map: &Map, reachable_goals: &mut HashMap<Pos, Vec<Pos>>, pos: Pos, height: u32, starting: Pos) -> u32 {
+        if height == 9 {
+            reachable_goals.entry(starting).or_default().push(pos);
+            return 1;
+        }
+        map.neighbors4(pos)
+            .filter(|next| map[*next] == height + 1)
+            .map(|next| traverse(map, reachable_goals, next, height + 1, starting))
+            .sum()
+    }
+
+    // now traverse each of those trailheads recursively finding the paths.
+    for trailhead in map.positions_of(&0) {
+        traverse(map, &mut reachable_goals, trailhead, 0, trailhead);
+    }
+
+    reachable_goals.iter_mut().for_each(|(_, v)| {
+        v.sort();
+        v.dedup();
+    });
+
+    reachable_goals.iter().fold(0, |acc, (_, v)| acc + v.len() as u32)
+}
+
+pub fn part_two(map: &Map) -> u32 {
+    fn traverse(map: &Map, pos: Pos, height: u32) -> u32 {
+        if height == 9 {
+            return 1;
+        }
+        map.neighbors4(pos)
+            .filter(|next| map[*next] == height + 1)
+            .map(|next| traverse(map, next, height + 1))
+            .sum()
+    }
+
+    map.positions_of(&0)
+        .map(|trailhead| traverse(map, trailhead, 0))
+        .sum()
+}
+
+pub struct Day10;
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Map;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_data(input)
+    }
+    fn part_one(map: &Self::Input) -> u32 {
+        part_one(map)
+    }
+    fn part_two(map: &Self::Input) -> u32 {
+        part_two(map)
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use crate::error::{parse_token, ParseError};
+use crate::solution::Solution;
+
+pub fn load_data(data: &str) -> Result<HashMap<u64, u64>, ParseError> {
+    data.lines()
+        .enumerate()
+        .flat_map(|(idx, line)| line.split_whitespace().map(move |num| parse_token::<u64>(idx, line, num)))
+        .map(|num| num.map(|num| (num, 1)))
+        .collect()
+}
+
+fn single_blink(stone: u64) -> [i64; 2] {
+    // ? Case when stone is 0
+    if stone == 0 {
+        return [1, -1];
+    }
+    // ? Case when stone has an even number of digits
+    let digits_str: String = stone.to_string();
+    let digits: std::str::Chars<'_> = digits_str.chars();
+    let ndigits: usize = digits.clone().count();
+    if ndigits.is_multiple_of(2) {
+        let left_stone: String = digits.clone().take(ndigits / 2).collect::<String>();
+        let right_stone: String = digits.clone().skip(ndigits / 2).collect::<String>();
+        let left_stone: u64 = left_stone.parse().expect("Couldn't parse left_stone");
+        let right_stone: u64 = right_stone.parse().expect("Couldn't parse right_stone");
+        return [left_stone as i64, right_stone as i64];
+    }
+    // ? Case when stone has an odd number of digits and is not 0
+    [stone as i64 * 2024, -1]
+}
+
+pub fn part_one(data: &HashMap<u64, u64>) -> u64 {
+    let data: &mut HashMap<u64, u64> = &mut data.clone();
+
+    for _ in 0..25 {
+        let mut new_data: HashMap<u64, u64> = HashMap::new();
+        for (stone, count) in data.iter() {
+            let stones: [i64; 2] = single_blink(*stone);
+            for stone in stones {
+                if stone == -1 {
+                    continue;
+                }
+                *new_data.entry(stone as u64).or_insert(0) += count;
+            }
+        }
+        *data = new_data;
+    }
+
+    data.values().sum()
+}
+
+pub fn part_two(data: &HashMap<u64, u64>) -> u64 {
+    let data: &mut HashMap<u64, u64> = &mut data.clone();
+
+    for _ in 0..75 {
+        let mut new_data: HashMap<u64, u64> = HashMap::new();
+        for (stone, count) in data.iter() {
+            let stones: [i64; 2] = single_blink(*stone);
+            for stone in stones {
+                if stone == -1 {
+                    continue;
+                }
+                *new_data.entry(stone as u64).or_insert(0) += count;
+            }
+        }
+        *data = new_data;
+    }
+
+    data.values().sum()
+}
+
+pub struct Day11;
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = HashMap<u64, u64>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_data(input)
+    }
+    fn part_one(data: &Self::Input) -> u64 {
+        part_one(data)
+    }
+    fn part_two(data: &Self::Input) -> u64 {
+        part_two(data)
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::ParseError;
+use crate::geometry::{Direction4, Pos};
+use crate::grid::Grid;
+use crate::solution::{Solution, Unsolved};
+
+#[derive(Debug, Clone, Eq)]
+pub struct Region {
+    symbol: char,
+    positions: Vec<Pos>,
+}
+impl PartialEq for Region {
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol == other.symbol
+            && self.positions.len() == other.positions.len()
+            && self
+                .positions
+                .iter()
+                .all(|pos| other.positions.contains(pos))
+    }
+}
+impl Region {
+    fn area(&self) -> u64 {
+        self.positions.len() as u64
+    }
+    fn perimeter(&self) -> u64 {
+        let cells: HashSet<Pos> = self.positions.iter().copied().collect();
+        let mut perimeter: u64 = 0;
+        for pos in &self.positions {
+            for dir in Direction4::ALL {
+                if !pos.checked_add(dir).is_some_and(|next| cells.contains(&next)) {
+                    perimeter += 1;
+                }
+            }
+        }
+        perimeter
+    }
+}
+pub type Bucket = Vec<Region>;
+pub type Map = HashMap<char, Bucket>;
+
+pub fn load_data(data: &str) -> Result<Map, ParseError> {
+    let regions: Grid<char> = Grid::parse(data, Some)?;
+
+    let mut map: Map = HashMap::new();
+
+    // depth first search in the regions matrix to find the various regions
+    let mut visited: Grid<bool> = Grid::new(regions.width(), regions.height(), false);
+
+    fn dfs(start: Pos, symb: char, visited: &mut Grid<bool>, regions: &Grid<char>) -> Vec<Pos> {
+        let mut stack: Vec<Pos> = vec![start];
+        let mut current_region: Vec<Pos> = Vec::new();
+        while let Some(pos) = stack.pop() {
+            if visited[pos] {
+                continue;
+            }
+            visited[pos] = true;
+            current_region.push(pos);
+            for next in regions.neighbors4(pos) {
+                if regions[next] == symb && !visited[next] {
+                    stack.push(next);
+                }
+            }
+        }
+        current_region
+    }
+
+    for pos in regions.positions() {
+        let symb = regions[pos];
+        if visited[pos] {
+            continue;
+        }
+        let new_region: Vec<Pos> = dfs(pos, symb, &mut visited, &regions);
+        let region: Region = Region {
+            symbol: symb,
+            positions: new_region,
+        };
+        map.entry(symb).or_default().push(region);
+    }
+
+    Ok(map)
+}
+
+pub fn part_one(map: &Map) -> u64 {
+    map.values()
+        .map(|bucket| {
+            let mut fence_price: u64 = 0;
+            for region in bucket {
+                fence_price += region.area() * region.perimeter();
+            }
+            fence_price
+        })
+        .sum()
+}
+
+pub struct Day12;
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Map;
+    type PartOne = u64;
+    type PartTwo = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_data(input)
+    }
+    fn part_one(map: &Self::Input) -> u64 {
+        part_one(map)
+    }
+    fn part_two(_: &Self::Input) -> Unsolved {
+        Unsolved
+    }
+}
@@ -0,0 +1,173 @@
+use regex::Regex;
+
+use crate::error::{parse_token, ParseError};
+use crate::geometry::{Direction, Direction8, Pos};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+/// The room of the real puzzle input.
+pub const MAP_SIZE: Point = Point { x: 101, y: 103 };
+/// The smaller room the puzzle's example runs in.
+pub const EXAMPLE_SIZE: Point = Point { x: 11, y: 7 };
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Robot {
+    pos: Point,
+    vel: Point,
+}
+
+/// The robots of a `size` room, each of which has to start inside it.
+pub fn load_data(data: &str, size: Point) -> Result<Vec<Robot>, ParseError> {
+    let mut robots: Vec<Robot> = Vec::new();
+    let robot_re: Regex = Regex::new(r"p=(-?\d+),(-?\d+)\s+v=(-?\d+),(-?\d+)").unwrap();
+    for (idx, line) in data.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let caps: regex::Captures<'_> = robot_re
+            .captures(line)
+            .ok_or_else(|| ParseError::at(idx, line, line, "expected a robot like `p=0,4 v=3,-3`"))?;
+        let field = |i: usize| parse_token::<i64>(idx, line, caps.get(i).unwrap().as_str());
+        let pos: Point = Point {
+            x: field(1)?,
+            y: field(2)?,
+        };
+        let vel: Point = Point {
+            x: field(3)?,
+            y: field(4)?,
+        };
+        if !(0..size.x).contains(&pos.x) || !(0..size.y).contains(&pos.y) {
+            return Err(ParseError::at(idx, line, caps.get(0).unwrap().as_str(), "robot starts outside of the map"));
+        }
+        robots.push(Robot { pos, vel });
+    }
+    Ok(robots)
+}
+
+pub struct Room {
+    pub size: Point,
+    pub robots: Vec<Robot>,
+}
+
+/// The robots of a `size` room, `MAP_SIZE` for the puzzle and `EXAMPLE_SIZE` for its example.
+pub fn load_room(data: &str, size: Point) -> Result<Room, ParseError> {
+    Ok(Room { size, robots: load_data(data, size)? })
+}
+
+// ? Move every robot once, teleporting across the walls however fast it goes.
+fn tick(robots: &mut [Robot], size: Point) {
+    for robot in robots.iter_mut() {
+        robot.pos.x = (robot.pos.x + robot.vel.x).rem_euclid(size.x);
+        robot.pos.y = (robot.pos.y + robot.vel.y).rem_euclid(size.y);
+    }
+}
+
+fn sobel(map: &Grid<u64>) -> Grid<char> {
+    let kernel_x: [[i64; 3]; 3] = [[-1, 0, 1], [-2, 0, 2], [-1, 0, 1]];
+    let kernel_y: [[i64; 3]; 3] = [[-1, -2, -1], [0, 0, 0], [1, 2, 1]];
+    let mut new_map: Grid<char> = Grid::new(map.width(), map.height(), '.');
+    let threshold: f64 = 4.0;
+
+    for (pos, count) in map.iter() {
+        if *count == 0 {
+            continue;
+        }
+        let mut gx: i64 = 0;
+        let mut gy: i64 = 0;
+        // ? The centre of both kernels is 0, so only the 8 neighbors contribute.
+        for dir in Direction8::ALL {
+            let Some(next) = map.step(pos, dir) else {
+                continue;
+            };
+            let (dx, dy) = dir.delta();
+            gx += kernel_x[(dy + 1) as usize][(dx + 1) as usize] * map[next] as i64;
+            gy += kernel_y[(dy + 1) as usize][(dx + 1) as usize] * map[next] as i64;
+        }
+        let g: f64 = ((gx * gx + gy * gy) as f64).sqrt();
+        if g > threshold {
+            new_map[pos] = '#';
+        } else {
+            new_map[pos] = '.';
+        }
+    }
+
+    new_map
+}
+
+// ? How many robots stand on each tile.
+fn occupancy(robots: &[Robot], size: Point) -> Grid<u64> {
+    let mut map: Grid<u64> = Grid::new(size.x as usize, size.y as usize, 0);
+    for robot in robots.iter() {
+        map[Pos::new(robot.pos.x as usize, robot.pos.y as usize)] += 1;
+    }
+    map
+}
+
+pub fn part_one(robots: &[Robot], size: Point) -> u64 {
+    let mut robots: Vec<Robot> = robots.to_vec();
+    for _ in 0..100 {
+        tick(&mut robots, size);
+    }
+    let mut robots_in_quadrant: [u64; 4] = [0; 4];
+    for robot in robots.iter() {
+        if robot.pos.x == size.x / 2 || robot.pos.y == size.y / 2 {
+            continue;
+        }
+        let quadrant: usize = if robot.pos.x < size.x / 2 {
+            if robot.pos.y < size.y / 2 {
+                0
+            } else {
+                2
+            }
+        } else {
+            if robot.pos.y < size.y / 2 {
+                1
+            } else {
+                3
+            }
+        };
+        robots_in_quadrant[quadrant] += 1;
+    }
+    robots_in_quadrant
+        .iter()
+        .fold(1, |acc, x| acc * *x)
+}
+
+pub fn part_two(robots: &[Robot], size: Point) -> u64 {
+    let mut robots: Vec<Robot> = robots.to_vec();
+    let mut max_sobel: u64 = 0;
+    let mut max_sobel_iter: u64 = 0;
+    for it in 0..10000 {
+        let sobel_map: Grid<char> = sobel(&occupancy(&robots, size));
+        let sobel: u64 = sobel_map.positions_of(&'#').count() as u64;
+        if sobel > max_sobel {
+            max_sobel = sobel;
+            max_sobel_iter = it;
+        }
+        tick(&mut robots, size);
+    }
+
+    max_sobel_iter
+}
+
+pub struct Day14;
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Room;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_room(input, MAP_SIZE)
+    }
+    fn part_one(room: &Self::Input) -> u64 {
+        part_one(&room.robots, room.size)
+    }
+    fn part_two(room: &Self::Input) -> u64 {
+        part_two(&room.robots, room.size)
+    }
+}
@@ -0,0 +1,143 @@
+use crate::error::ParseError;
+use crate::geometry::{Direction4, Pos};
+use crate::grid::Grid;
+use crate::solution::{Solution, Unsolved};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Wall,
+    Empty,
+    Box,
+    Robot,
+}
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let c = match self {
+            Tile::Wall => '#',
+            Tile::Empty => '.',
+            Tile::Box => 'O',
+            Tile::Robot => '@',
+        };
+        write!(f, "{}", c)
+    }
+}
+pub type Map = Grid<Tile>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Robot {
+    pos: Pos,
+    path: Vec<Direction4>,
+}
+
+pub fn load_data(data: &str) -> Result<(Map, Robot), ParseError> {
+    let mut lines = data.lines().enumerate();
+    let mut map_lines: Vec<&str> = Vec::new();
+    loop {
+        let (_, line) = lines
+            .next()
+            .ok_or_else(|| ParseError::end_of_input(map_lines.len(), "expected a blank line before the moves"))?;
+        if line.is_empty() {
+            break;
+        }
+        map_lines.push(line);
+    }
+    let map: Map = Grid::parse(&map_lines.join("\n"), |c| match c {
+        '#' => Some(Tile::Wall),
+        '.' => Some(Tile::Empty),
+        'O' => Some(Tile::Box),
+        '@' => Some(Tile::Robot),
+        _ => None,
+    })?;
+    let robot_pos: Pos = map
+        .find(|tile| *tile == Tile::Robot)
+        .ok_or_else(|| ParseError::end_of_input(map_lines.len(), "no robot on the map"))?;
+
+    let mut path: Vec<Direction4> = Vec::new();
+    for (idx, line) in lines {
+        for (col, c) in line.char_indices() {
+            let dir: Direction4 = Direction4::from_arrow(c).ok_or_else(|| {
+                ParseError::at(idx, line, &line[col..col + c.len_utf8()], "invalid direction character")
+            })?;
+            path.push(dir);
+        }
+    }
+
+    let robot: Robot = Robot {
+        pos: robot_pos,
+        path,
+    };
+
+    Ok((map, robot))
+}
+
+pub fn part_one(map: &Map, robot: &Robot) -> u64 {
+    // ? Simulate the movement of the robot inside of the map following the path.
+    // ? It moves around boxes but not walls.
+    let mut map: Map = map.clone();
+    let mut robot: Robot = robot.clone();
+
+    for dir in robot.path.iter() {
+        let Some(next) = map.step(robot.pos, *dir) else {
+            continue;
+        };
+        match map[next] {
+            Tile::Wall => continue,
+            Tile::Empty => {
+                map[robot.pos] = Tile::Empty;
+                robot.pos = next;
+                map[next] = Tile::Robot;
+            }
+            Tile::Box => {
+                let mut boxes: Vec<Pos> = Vec::new();
+                let mut wall: bool = false;
+                for pos in map.walk(next, *dir).skip(1) {
+                    match map[pos] {
+                        Tile::Wall => {
+                            wall = true;
+                            break;
+                        }
+                        Tile::Empty => {
+                            boxes.push(pos);
+                            break;
+                        }
+                        Tile::Box => {
+                            boxes.push(pos);
+                        }
+                        _ => break,
+                    }
+                }
+                if boxes.is_empty() || wall {
+                    continue;
+                }
+                map[robot.pos] = Tile::Empty;
+                robot.pos = next;
+                map[robot.pos] = Tile::Robot;
+                for pos in boxes.iter() {
+                    map[*pos] = Tile::Box;
+                }
+            }
+            _ => continue,
+        }
+    }
+    map.positions_of(&Tile::Box)
+        .map(|pos| (100 * pos.y + pos.x) as u64)
+        .sum()
+}
+
+pub struct Day15;
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = (Map, Robot);
+    type PartOne = u64;
+    type PartTwo = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_data(input)
+    }
+    fn part_one((map, robot): &Self::Input) -> u64 {
+        part_one(map, robot)
+    }
+    fn part_two(_: &Self::Input) -> Unsolved {
+        Unsolved
+    }
+}
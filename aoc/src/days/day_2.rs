@@ -0,0 +1,321 @@
+use std::fmt;
+use std::fmt::Write;
+
+use crate::error::{parse_token, ParseError};
+use crate::solution::Solution;
+
+pub fn load_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, s)| {
+            s.split_whitespace()
+                .map(|n| parse_token::<i32>(idx, s, n))
+                .collect()
+        })
+        .collect()
+}
+
+/// Why a step between two levels breaks the rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    ZeroStep,
+    /// Not zero, but below the rule's `min_step`.
+    StepTooSmall,
+    StepTooLarge,
+    DirectionChange,
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ViolationKind::ZeroStep => write!(f, "zero step"),
+            ViolationKind::StepTooSmall => write!(f, "step too small"),
+            ViolationKind::StepTooLarge => write!(f, "step too large"),
+            ViolationKind::DirectionChange => write!(f, "direction change"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    /// Safe once the levels at these indices are dropped, a single one under the puzzle's rule.
+    SafeAfterRemoving(Vec<usize>),
+    /// `first_violation` is the index of the level whose step from the previous one breaks the rule.
+    Unsafe { first_violation: usize, kind: ViolationKind },
+}
+
+/// What makes a report safe: every step between kept levels is between `min_step` and `max_step`
+/// (in absolute value), all in the same direction when `monotonic`, after dropping at most
+/// `max_removals` levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyRule {
+    pub min_step: i64,
+    pub max_step: i64,
+    pub monotonic: bool,
+    pub max_removals: usize,
+}
+
+impl SafetyRule {
+    pub const PART_ONE: SafetyRule = SafetyRule { min_step: 1, max_step: 3, monotonic: true, max_removals: 0 };
+    pub const PART_TWO: SafetyRule = SafetyRule { max_removals: 1, ..SafetyRule::PART_ONE };
+
+    pub fn is_safe(&self, levels: &[i32]) -> bool {
+        self.check(levels).is_some()
+    }
+
+    pub fn verdict(&self, levels: &[i32]) -> Verdict {
+        match self.check(levels) {
+            Some(dropped) if dropped.is_empty() => Verdict::Safe,
+            Some(dropped) => Verdict::SafeAfterRemoving(dropped),
+            None => {
+                let (first_violation, kind) = self.first_violation(levels).expect("an unsafe report breaks the rule somewhere");
+                Verdict::Unsafe { first_violation, kind }
+            }
+        }
+    }
+
+    /// The first level, without removing any, whose step from the previous one breaks the rule.
+    /// The direction is set by the first step that isn't zero.
+    pub fn first_violation(&self, levels: &[i32]) -> Option<(usize, ViolationKind)> {
+        let mut direction: i64 = 0;
+        for i in 1..levels.len() {
+            let diff: i64 = levels[i] as i64 - levels[i - 1] as i64;
+            if diff.abs() < self.min_step {
+                let kind: ViolationKind = if diff == 0 { ViolationKind::ZeroStep } else { ViolationKind::StepTooSmall };
+                return Some((i, kind));
+            }
+            if diff.abs() > self.max_step {
+                return Some((i, ViolationKind::StepTooLarge));
+            }
+            if self.monotonic && diff != 0 {
+                if direction != 0 && diff.signum() != direction {
+                    return Some((i, ViolationKind::DirectionChange));
+                }
+                direction = diff.signum();
+            }
+        }
+        None
+    }
+
+    /// The fewest indices to drop for `levels` to be safe, `None` if that takes more than `max_removals`.
+    pub fn check(&self, levels: &[i32]) -> Option<Vec<usize>> {
+        let step = |diff: i64| (self.min_step..=self.max_step).contains(&diff);
+        if !self.monotonic {
+            return self.fewest_removals(levels, |diff| step(diff.abs()));
+        }
+        let increasing: Option<Vec<usize>> = self.fewest_removals(levels, step);
+        let decreasing: Option<Vec<usize>> = self.fewest_removals(levels, |diff| step(-diff));
+        match (increasing, decreasing) {
+            (Some(up), Some(down)) if down.len() < up.len() => Some(down),
+            (up, down) => up.or(down),
+        }
+    }
+
+    // ? Keep the longest chain of levels whose consecutive steps are all `allowed`. A kept level can only
+    // ? follow one of the `max_removals + 1` levels before it, so this is O(n * K).
+    fn fewest_removals(&self, levels: &[i32], allowed: impl Fn(i64) -> bool) -> Option<Vec<usize>> {
+        let n: usize = levels.len();
+        let k: usize = self.max_removals;
+        // ? removals[j]: the fewest levels dropped before j when j is kept, reached from previous[j]
+        let mut removals: Vec<Option<usize>> = vec![None; n];
+        let mut previous: Vec<Option<usize>> = vec![None; n];
+        for j in 0..n {
+            if j <= k {
+                removals[j] = Some(j);
+            }
+            for i in j.saturating_sub(k + 1)..j {
+                let Some(before) = removals[i] else {
+                    continue;
+                };
+                let cost: usize = before + (j - i - 1);
+                let diff: i64 = levels[j] as i64 - levels[i] as i64;
+                if cost <= k && allowed(diff) && removals[j].is_none_or(|best| cost < best) {
+                    removals[j] = Some(cost);
+                    previous[j] = Some(i);
+                }
+            }
+        }
+
+        if n == 0 {
+            return Some(Vec::new());
+        }
+        let (last, total) = (n.saturating_sub(k + 1)..n)
+            .filter_map(|j| removals[j].map(|cost| (j, cost + (n - 1 - j))))
+            .min_by_key(|(_, total)| *total)?;
+        if total > k {
+            return None;
+        }
+        let mut kept: Vec<bool> = vec![false; n];
+        let mut current: Option<usize> = Some(last);
+        while let Some(j) = current {
+            kept[j] = true;
+            current = previous[j];
+        }
+        Some((0..n).filter(|i| !kept[*i]).collect())
+    }
+}
+
+pub fn part_one(reports: &[Vec<i32>]) -> usize {
+    reports
+        .iter()
+        .filter(|r| SafetyRule::PART_ONE.is_safe(r))
+        .count()
+}
+
+pub fn part_two(reports: &[Vec<i32>]) -> usize {
+    reports
+        .iter()
+        .filter(|r| SafetyRule::PART_TWO.is_safe(r))
+        .count()
+}
+
+/// Every report with its verdict under `rule`: dropped levels in parentheses, the level breaking
+/// the rule in brackets.
+pub fn explain(reports: &[Vec<i32>], rule: &SafetyRule, unsafe_only: bool) -> String {
+    let mut out: String = String::new();
+    let mut safe: usize = 0;
+    for (idx, report) in reports.iter().enumerate() {
+        let verdict: Verdict = rule.verdict(report);
+        if !matches!(verdict, Verdict::Unsafe { .. }) {
+            safe += 1;
+            if unsafe_only {
+                continue;
+            }
+        }
+        let levels: Vec<String> = report
+            .iter()
+            .enumerate()
+            .map(|(i, level)| match &verdict {
+                Verdict::SafeAfterRemoving(dropped) if dropped.contains(&i) => format!("({})", level),
+                Verdict::Unsafe { first_violation, .. } if *first_violation == i => format!("[{}]", level),
+                _ => level.to_string(),
+            })
+            .collect();
+        let status: String = match &verdict {
+            Verdict::Safe => "safe".to_string(),
+            Verdict::SafeAfterRemoving(dropped) => format!("safe after removing {:?}", dropped),
+            Verdict::Unsafe { first_violation, kind } => format!("unsafe, {} at index {}", kind, first_violation),
+        };
+        writeln!(out, "{:>5}: {:<40}  {}", idx + 1, levels.join(" "), status).unwrap();
+    }
+    writeln!(out, "{} of {} reports are safe", safe, reports.len()).unwrap();
+    out
+}
+
+pub struct Day2;
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<Vec<i32>>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_input(input)
+    }
+    fn part_one(reports: &Self::Input) -> usize {
+        part_one(reports)
+    }
+    fn part_two(reports: &Self::Input) -> usize {
+        part_two(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{examples_dir, Rng};
+
+    // ? The original checks: one pass for K = 0, then trying every single removal for K = 1.
+    fn brute_force_safe(report: &[i32]) -> bool {
+        let mut increasing: bool = true;
+        let mut decreasing: bool = true;
+        for i in 1..report.len() {
+            increasing &= report[i] > report[i - 1];
+            decreasing &= report[i] < report[i - 1];
+            if !(1..=3).contains(&(report[i] - report[i - 1]).abs()) {
+                return false;
+            }
+        }
+        increasing || decreasing
+    }
+
+    fn brute_force_safe_removing_one(report: &[i32]) -> bool {
+        brute_force_safe(report)
+            || (0..report.len()).any(|i| {
+                let mut shorter: Vec<i32> = report.to_vec();
+                shorter.remove(i);
+                brute_force_safe(&shorter)
+            })
+    }
+
+    // ? Small levels close to each other, so every kind of violation shows up often.
+    fn reports() -> Vec<Vec<i32>> {
+        let mut rng: Rng = Rng::new(0x2024);
+        let mut reports: Vec<Vec<i32>> = (0..5000)
+            .map(|_| {
+                let mut level: i32 = rng.below(20) as i32;
+                (0..rng.below(9)).map(|_| {
+                    level += rng.below(11) as i32 - 5;
+                    level
+                })
+                .collect()
+            })
+            .collect();
+        reports.extend(load_input(&std::fs::read_to_string(examples_dir().join("day2_part1_1.txt")).unwrap()).unwrap());
+        reports
+    }
+
+    #[test]
+    fn matches_brute_force_without_removals() {
+        for report in reports() {
+            assert_eq!(SafetyRule::PART_ONE.is_safe(&report), brute_force_safe(&report), "{:?}", report);
+        }
+    }
+
+    #[test]
+    fn matches_brute_force_with_one_removal() {
+        for report in reports() {
+            let dropped: Option<Vec<usize>> = SafetyRule::PART_TWO.check(&report);
+            assert_eq!(dropped.is_some(), brute_force_safe_removing_one(&report), "{:?}", report);
+            if let Some(dropped) = dropped {
+                let kept: Vec<i32> = (0..report.len()).filter(|i| !dropped.contains(i)).map(|i| report[i]).collect();
+                assert!(dropped.len() <= 1 && brute_force_safe(&kept), "{:?} dropping {:?}", report, dropped);
+            }
+        }
+    }
+
+    #[test]
+    fn explains_each_verdict() {
+        let rule: SafetyRule = SafetyRule::PART_TWO;
+        assert_eq!(rule.verdict(&[7, 6, 4, 2, 1]), Verdict::Safe);
+        assert_eq!(rule.verdict(&[1, 3, 2, 4, 5]), Verdict::SafeAfterRemoving(vec![2]));
+        assert_eq!(rule.verdict(&[1, 2, 7, 8, 9]), Verdict::Unsafe { first_violation: 2, kind: ViolationKind::StepTooLarge });
+        assert_eq!(rule.verdict(&[9, 7, 6, 6, 6]), Verdict::Unsafe { first_violation: 3, kind: ViolationKind::ZeroStep });
+        assert_eq!(
+            rule.verdict(&[1, 3, 2, 5, 4]),
+            Verdict::Unsafe { first_violation: 2, kind: ViolationKind::DirectionChange }
+        );
+        let wide: SafetyRule = SafetyRule { min_step: 2, ..SafetyRule::PART_ONE };
+        assert_eq!(wide.first_violation(&[1, 3, 4]), Some((2, ViolationKind::StepTooSmall)));
+
+        let explained: String = explain(&[vec![1, 3, 2, 4, 5], vec![9, 7, 6, 6, 6]], &rule, false);
+        assert!(explained.contains("1 3 (2) 4 5"));
+        assert!(explained.contains("9 7 6 [6] 6"));
+        assert!(explained.ends_with("1 of 2 reports are safe\n"));
+    }
+
+    #[test]
+    fn returns_the_dropped_levels() {
+        let rule: SafetyRule = SafetyRule { max_removals: 2, ..SafetyRule::PART_ONE };
+        assert_eq!(rule.check(&[7, 6, 4, 2, 1]), Some(vec![]));
+        assert_eq!(rule.check(&[1, 3, 2, 4, 5]), Some(vec![2]));
+        assert_eq!(rule.check(&[1, 9, 2, 9, 3]), Some(vec![1, 3]));
+        assert_eq!(rule.check(&[1, 9, 2, 9, 3, 9]), None);
+
+        let bumpy: SafetyRule = SafetyRule { min_step: 0, max_step: 2, monotonic: false, max_removals: 0 };
+        assert!(bumpy.is_safe(&[1, 3, 3, 1, 2]));
+        assert!(!bumpy.is_safe(&[1, 4]));
+    }
+}
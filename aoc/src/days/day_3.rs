@@ -0,0 +1,378 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+/// What an instruction does, independently of how it is spelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Add the product of both operands to the total.
+    Mul,
+    /// Add the operand to the total.
+    Add,
+    /// Subtract the operand from the total.
+    Sub,
+    /// Enable, closing every open `Skip` too.
+    Do,
+    /// Disable until the next `Do`.
+    Dont,
+    /// Disable until the matching `Resume`, nesting.
+    Skip,
+    Resume,
+}
+
+impl Op {
+    pub fn arity(self) -> usize {
+        match self {
+            Op::Mul => 2,
+            Op::Add | Op::Sub => 1,
+            Op::Do | Op::Dont | Op::Skip | Op::Resume => 0,
+        }
+    }
+}
+
+/// An instruction spelled `name(operands...)` in the memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub op: Op,
+}
+
+/// The instructions of the puzzle.
+pub const PUZZLE: &[Instruction] = &[
+    Instruction { name: "mul", op: Op::Mul },
+    Instruction { name: "do", op: Op::Do },
+    Instruction { name: "don't", op: Op::Dont },
+];
+
+/// The puzzle's instructions plus `add(a)`, `sub(a)` and the nesting `skip()` ... `resume()`.
+pub const EXTENDED: &[Instruction] = &[
+    Instruction { name: "mul", op: Op::Mul },
+    Instruction { name: "do", op: Op::Do },
+    Instruction { name: "don't", op: Op::Dont },
+    Instruction { name: "add", op: Op::Add },
+    Instruction { name: "sub", op: Op::Sub },
+    Instruction { name: "skip", op: Op::Skip },
+    Instruction { name: "resume", op: Op::Resume },
+];
+
+/// An instruction found in the corrupted memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token<'a> {
+    Mul(u32, u32),
+    Add(u32),
+    Sub(u32),
+    Do,
+    Dont,
+    Skip,
+    Resume,
+    /// An instruction registered with `Lexer::with_instruction`.
+    Other { name: &'a str, args: Vec<u32> },
+}
+
+impl Token<'_> {
+    fn new(op: Op, args: &[u32]) -> Token<'static> {
+        match op {
+            Op::Mul => Token::Mul(args[0], args[1]),
+            Op::Add => Token::Add(args[0]),
+            Op::Sub => Token::Sub(args[0]),
+            Op::Do => Token::Do,
+            Op::Dont => Token::Dont,
+            Op::Skip => Token::Skip,
+            Op::Resume => Token::Resume,
+        }
+    }
+}
+
+/// Operands are 1 to 3 digits long, anything longer is corrupted.
+const MAX_DIGITS: usize = 3;
+
+/// Scans the memory once, left to right, yielding every well-formed instruction of its table with
+/// the byte range it spans, and skipping everything else.
+pub struct Lexer<'a> {
+    memory: &'a str,
+    pos: usize,
+    table: &'a [Instruction],
+    extra: Vec<(&'a str, usize)>,
+}
+
+impl<'a> Lexer<'a> {
+    /// A lexer for the puzzle's own instructions.
+    pub fn new(memory: &'a str) -> Lexer<'a> {
+        Lexer::with_table(memory, PUZZLE)
+    }
+
+    pub fn with_table(memory: &'a str, table: &'a [Instruction]) -> Lexer<'a> {
+        Lexer { memory, pos: 0, table, extra: Vec::new() }
+    }
+
+    /// Also recognize `name(a,b,...)` with `arity` operands, yielded as `Token::Other`.
+    pub fn with_instruction(mut self, name: &'a str, arity: usize) -> Lexer<'a> {
+        self.extra.push((name, arity));
+        self
+    }
+
+    // ? `(a,b,...)` with exactly `arity` operands starting at `start`, and where it ends
+    fn operands(&self, start: usize, arity: usize) -> Option<(Vec<u32>, usize)> {
+        let bytes: &[u8] = self.memory.as_bytes();
+        let mut pos: usize = start;
+        if bytes.get(pos) != Some(&b'(') {
+            return None;
+        }
+        pos += 1;
+        let mut args: Vec<u32> = Vec::with_capacity(arity);
+        for i in 0..arity {
+            if i > 0 {
+                if bytes.get(pos) != Some(&b',') {
+                    return None;
+                }
+                pos += 1;
+            }
+            let digits: usize = bytes[pos..].iter().take_while(|b| b.is_ascii_digit()).count();
+            if !(1..=MAX_DIGITS).contains(&digits) {
+                return None;
+            }
+            args.push(self.memory[pos..pos + digits].parse().unwrap());
+            pos += digits;
+        }
+        if bytes.get(pos) != Some(&b')') {
+            return None;
+        }
+        Some((args, pos + 1))
+    }
+
+    fn token_at(&self, pos: usize) -> Option<(Token<'a>, usize)> {
+        let rest: &str = &self.memory[pos..];
+        // ? `do` is a prefix of `don't`, so a failed match has to fall through to the next entry
+        let builtin = self.table.iter().filter(|instruction| rest.starts_with(instruction.name)).find_map(|instruction| {
+            let (args, end) = self.operands(pos + instruction.name.len(), instruction.op.arity())?;
+            Some((Token::new(instruction.op, &args), end))
+        });
+        builtin.or_else(|| {
+            self.extra.iter().filter(|(name, _)| rest.starts_with(name)).find_map(|(name, arity)| {
+                let (args, end) = self.operands(pos + name.len(), *arity)?;
+                Some((Token::Other { name, args }, end))
+            })
+        })
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = (Range<usize>, Token<'a>);
+
+    fn next(&mut self) -> Option<(Range<usize>, Token<'a>)> {
+        while self.pos < self.memory.len() {
+            // ? A broken instruction may hide a valid one inside it, so only skip one byte on a miss
+            let start: usize = self.pos;
+            match self.token_at(start) {
+                Some((token, end)) => {
+                    self.pos = end;
+                    return Some((start..end, token));
+                }
+                None => self.pos += 1,
+            }
+            while !self.memory.is_char_boundary(self.pos) {
+                self.pos += 1;
+            }
+        }
+        None
+    }
+}
+
+/// A running total that reports overflow instead of wrapping.
+pub trait Total: Copy + Default + From<u32> + fmt::Display {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_total {
+    ($($t:ty),*) => {
+        $(impl Total for $t {
+            fn checked_add(self, other: $t) -> Option<$t> {
+                <$t>::checked_add(self, other)
+            }
+            fn checked_sub(self, other: $t) -> Option<$t> {
+                <$t>::checked_sub(self, other)
+            }
+            fn checked_mul(self, other: $t) -> Option<$t> {
+                <$t>::checked_mul(self, other)
+            }
+        })*
+    };
+}
+impl_total!(u32, u64, u128);
+
+/// The total left the range of its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+// ? The arithmetic instructions, applied to `total`; anything else leaves it alone.
+fn apply<N: Total>(total: N, token: &Token) -> Result<N, Overflow> {
+    let result: Option<N> = match *token {
+        Token::Mul(a, b) => N::from(a).checked_mul(N::from(b)).and_then(|product| total.checked_add(product)),
+        Token::Add(a) => total.checked_add(N::from(a)),
+        Token::Sub(a) => total.checked_sub(N::from(a)),
+        _ => Some(total),
+    };
+    result.ok_or(Overflow)
+}
+
+/// The state the interpreter threads through the instructions.
+pub trait Machine {
+    fn execute(&mut self, token: &Token) -> Result<(), Overflow>;
+}
+
+/// Applies every arithmetic instruction, ignoring toggles.
+#[derive(Debug, Clone, Default)]
+pub struct Summer<N> {
+    pub total: N,
+}
+
+impl<N: Total> Machine for Summer<N> {
+    fn execute(&mut self, token: &Token) -> Result<(), Overflow> {
+        self.total = apply(self.total, token)?;
+        Ok(())
+    }
+}
+
+/// Applies the arithmetic instructions met while enabled. `don't()` disables until the next `do()`,
+/// while `skip()` disables until its own `resume()`, so those nest.
+#[derive(Debug, Clone, Default)]
+pub struct Toggled<N> {
+    pub disabled: usize,
+    pub total: N,
+}
+
+impl<N: Total> Machine for Toggled<N> {
+    fn execute(&mut self, token: &Token) -> Result<(), Overflow> {
+        match token {
+            Token::Do => self.disabled = 0,
+            Token::Dont => self.disabled = self.disabled.max(1),
+            Token::Skip => self.disabled += 1,
+            Token::Resume => self.disabled = self.disabled.saturating_sub(1),
+            _ if self.disabled == 0 => self.total = apply(self.total, token)?,
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+/// The instruction that made the total overflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: `{}` overflows the total", self.line, self.column, self.text)
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// Run the instructions of `table` found in `memory` through `machine`.
+pub fn evaluate<M: Machine>(memory: &str, table: &[Instruction], mut machine: M) -> Result<M, EvalError> {
+    for (span, token) in Lexer::with_table(memory, table) {
+        if machine.execute(&token).is_err() {
+            let before: &str = &memory[..span.start];
+            let line_start: usize = before.rfind('\n').map_or(0, |idx| idx + 1);
+            return Err(EvalError {
+                line: before.matches('\n').count() + 1,
+                column: before[line_start..].chars().count() + 1,
+                text: memory[span].to_string(),
+            });
+        }
+    }
+    Ok(machine)
+}
+
+pub fn part_one(memory: &str) -> Result<u64, EvalError> {
+    Ok(evaluate(memory, PUZZLE, Summer::default())?.total)
+}
+
+pub fn part_two(memory: &str) -> Result<u64, EvalError> {
+    Ok(evaluate(memory, PUZZLE, Toggled::default())?.total)
+}
+
+pub struct Day3;
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = String;
+    type PartOne = Result<u64, EvalError>;
+    type PartTwo = Result<u64, EvalError>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
+    }
+    fn part_one(memory: &Self::Input) -> Result<u64, EvalError> {
+        part_one(memory)
+    }
+    fn part_two(memory: &Self::Input) -> Result<u64, EvalError> {
+        part_two(memory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens<'a>(lexer: Lexer<'a>) -> Vec<Token<'a>> {
+        lexer.map(|(_, token)| token).collect()
+    }
+
+    #[test]
+    fn lexes_in_a_single_pass() {
+        let memory: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(
+            tokens(Lexer::new(memory)),
+            vec![Token::Mul(2, 4), Token::Dont, Token::Mul(5, 5), Token::Mul(11, 8), Token::Do, Token::Mul(8, 5)]
+        );
+        assert_eq!(Lexer::new(memory).next(), Some((1..9, Token::Mul(2, 4))));
+    }
+
+    #[test]
+    fn operands_have_one_to_three_digits() {
+        let memory: &str = "mul(1234,5)mul(123,4)mul(,4)mul(4)mul(mul(7,8)mul( 1,2)";
+        assert_eq!(tokens(Lexer::new(memory)), vec![Token::Mul(123, 4), Token::Mul(7, 8)]);
+    }
+
+    #[test]
+    fn registered_instructions_are_hooked_in() {
+        let lexer: Lexer = Lexer::new("add(1,2,3)mul(2,2)neg(7)négneg(8)").with_instruction("add", 3).with_instruction("neg", 1);
+        assert_eq!(
+            tokens(lexer),
+            vec![
+                Token::Other { name: "add", args: vec![1, 2, 3] },
+                Token::Mul(2, 2),
+                Token::Other { name: "neg", args: vec![7] },
+                Token::Other { name: "neg", args: vec![8] },
+            ]
+        );
+    }
+
+    #[test]
+    fn extended_table_nests_skips() {
+        let memory: &str = "add(5)skip()mul(2,2)skip()do()mul(3,3)resume()sub(1)resume()mul(1,4)don't()add(9)do()sub(2)";
+        // ? `do()` closes every open `skip()`, the stray `resume()`s then do nothing
+        assert_eq!(evaluate(memory, EXTENDED, Toggled::<u64>::default()).unwrap().total, 5 + 9 - 1 + 4 - 2);
+        assert_eq!(evaluate(memory, EXTENDED, Summer::<u64>::default()).unwrap().total, 5 + 4 + 9 - 1 + 4 + 9 - 2);
+        // ? The puzzle's table sees none of the extra instructions
+        assert_eq!(evaluate(memory, PUZZLE, Summer::<u64>::default()).unwrap().total, 4 + 9 + 4);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let memory: &str = "add(1)\nmul(2,2)sub(9)";
+        let err: EvalError = evaluate(memory, EXTENDED, Summer::<u64>::default()).unwrap_err();
+        assert_eq!(err, EvalError { line: 2, column: 9, text: "sub(9)".to_string() });
+
+        let big: String = "mul(999,999)".repeat(5000);
+        assert!(evaluate(&big, PUZZLE, Summer::<u32>::default()).is_err());
+        assert_eq!(evaluate(&big, PUZZLE, Summer::<u128>::default()).unwrap().total, 999 * 999 * 5000);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::error::ParseError;
+use crate::geometry::{Direction8, Pos};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+
+pub type Map = Grid<u8>;
+
+
+pub fn parse_input(input: &str) -> Result<Map, ParseError> {
+    Grid::parse(input, |c| c.is_ascii().then_some(c as u8))
+}
+
+/// One occurrence of a word: which one of the list, the cell of its first letter and the way it reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Match {
+    pub word: usize,
+    pub start: Pos,
+    pub dir: Direction8,
+}
+
+/// Looks for words along the rows, columns and diagonals of a grid, in all eight directions.
+/// Matches may overlap and share letters; a palindrome is reported once rather than once per way
+/// it reads.
+#[derive(Debug, Clone, Copy)]
+pub struct WordSearch<'a> {
+    map: &'a Map,
+    wrap: bool,
+}
+
+impl<'a> WordSearch<'a> {
+    pub fn new(map: &'a Map) -> WordSearch<'a> {
+        WordSearch { map, wrap: false }
+    }
+
+    /// Let words run off an edge and come back in from the opposite one.
+    pub fn with_wrap(mut self, wrap: bool) -> WordSearch<'a> {
+        self.wrap = wrap;
+        self
+    }
+
+    fn step(&self, pos: Pos, dir: Direction8) -> Option<Pos> {
+        if self.wrap {
+            Some(pos.wrapping_add(dir, self.map.width(), self.map.height()))
+        } else {
+            self.map.step(pos, dir)
+        }
+    }
+
+    // ? Whether `word` reads from `start` towards `dir`
+    fn reads(&self, word: &[u8], start: Pos, dir: Direction8) -> bool {
+        let mut pos: Pos = start;
+        for (idx, letter) in word.iter().enumerate() {
+            if idx > 0 {
+                match self.step(pos, dir) {
+                    Some(next) => pos = next,
+                    None => return false,
+                }
+            }
+            if self.map[pos] != *letter {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Every match of every word, in the order of the grid, then of the directions, then of `words`.
+    pub fn find(&self, words: &[&str]) -> Vec<Match> {
+        let mut matches: Vec<Match> = Vec::new();
+        for start in self.map.positions() {
+            for dir in Direction8::ALL {
+                for (idx, word) in words.iter().enumerate() {
+                    let word: &[u8] = word.as_bytes();
+                    if word.first() != Some(&self.map[start]) || !self.reads(word, start, dir) {
+                        continue;
+                    }
+                    // ? A palindrome reads the same from its other end, keep only one way: a single
+                    // ? letter reads the same in every direction, longer ones backwards.
+                    let palindrome: bool = word.iter().eq(word.iter().rev());
+                    if palindrome && (word.len() == 1 && dir != Direction8::Right || word.len() > 1 && dir as usize >= 4) {
+                        continue;
+                    }
+                    matches.push(Match { word: idx, start, dir });
+                }
+            }
+        }
+        matches
+    }
+
+    pub fn count(&self, words: &[&str]) -> usize {
+        self.find(words).len()
+    }
+}
+
+pub fn part_one(map: &Map) -> usize {
+    WordSearch::new(map).count(&["XMAS"])
+}
+
+/// One placement of a template: the top-left corner and which of its `variants` sits there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Placement {
+    pub anchor: Pos,
+    pub variant: usize,
+}
+
+/// A small 2D shape to look for, where `None` cells match any letter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Template {
+    cells: Grid<Option<u8>>,
+}
+
+impl Template {
+    /// Rows separated by `/` or newlines, with `.` as the wildcard: `M.S/.A./M.S`.
+    pub fn parse(pattern: &str) -> Result<Template, ParseError> {
+        let rows: Vec<&str> = pattern.split(['/', '\n']).map(str::trim).filter(|row| !row.is_empty()).collect();
+        if rows.is_empty() {
+            return Err(ParseError::end_of_input(0, "expected at least one row"));
+        }
+        let cells: Grid<Option<u8>> = Grid::parse(&rows.join("\n"), |c| match c {
+            '.' => Some(None),
+            c if c.is_ascii() => Some(Some(c as u8)),
+            _ => None,
+        })?;
+        Ok(Template { cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Turned a quarter clockwise.
+    pub fn rotate(&self) -> Template {
+        let (width, height) = (self.width(), self.height());
+        let cells: Vec<Option<u8>> = (0..width)
+            .flat_map(|y| (0..height).map(move |x| Pos::new(y, height - 1 - x)))
+            .map(|pos| self.cells[pos])
+            .collect();
+        Template { cells: Grid::from_vec(height, width, cells).unwrap() }
+    }
+
+    /// Mirrored left to right.
+    pub fn reflect(&self) -> Template {
+        let width: usize = self.width();
+        let cells: Vec<Option<u8>> = self.cells.positions().map(|pos| self.cells[Pos::new(width - 1 - pos.x, pos.y)]).collect();
+        Template { cells: Grid::from_vec(width, self.height(), cells).unwrap() }
+    }
+
+    /// Every distinct rotation and reflection, this one first.
+    pub fn variants(&self) -> Vec<Template> {
+        let mut variants: Vec<Template> = Vec::with_capacity(8);
+        for start in [self.clone(), self.reflect()] {
+            let mut template: Template = start;
+            for _ in 0..4 {
+                let next: Template = template.rotate();
+                if !variants.contains(&template) {
+                    variants.push(template);
+                }
+                template = next;
+            }
+        }
+        variants
+    }
+
+    // ? Whether this exact orientation sits on `map` with its top-left corner at `anchor`
+    fn fits(&self, map: &Map, anchor: Pos) -> bool {
+        self.cells.iter().all(|(pos, cell)| match cell {
+            None => map.contains(Pos::new(anchor.x + pos.x, anchor.y + pos.y)),
+            Some(letter) => map.get(Pos::new(anchor.x + pos.x, anchor.y + pos.y)) == Some(letter),
+        })
+    }
+
+    /// Every placement of the shape in any orientation, row by row, then in the order of `variants`.
+    /// Orientations that fit at the same corner cover different cells, so each one is reported.
+    pub fn matches(&self, map: &Map) -> Vec<Placement> {
+        let variants: Vec<Template> = self.variants();
+        map.positions()
+            .flat_map(|anchor| {
+                let fitting = variants.iter().enumerate().filter(move |(_, variant)| variant.fits(map, anchor));
+                fitting.map(move |(variant, _)| Placement { anchor, variant })
+            })
+            .collect()
+    }
+
+    pub fn count(&self, map: &Map) -> usize {
+        self.matches(map).len()
+    }
+}
+
+pub fn part_two(map: &Map) -> usize {
+    let x_mas: Template = Template::parse("M.S/.A./M.S").unwrap();
+    x_mas.count(map)
+}
+
+pub struct Day4;
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Map;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+    fn part_one(map: &Self::Input) -> usize {
+        part_one(map)
+    }
+    fn part_two(map: &Self::Input) -> usize {
+        part_two(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(rows: &str) -> Map {
+        parse_input(rows).unwrap()
+    }
+
+    #[test]
+    fn finds_words_in_every_direction() {
+        let map: Map = map("SAMX\nAXMA\nMXAM\nXMAS\n");
+        let matches: Vec<Match> = WordSearch::new(&map).find(&["XMAS"]);
+        assert_eq!(
+            matches,
+            vec![
+                Match { word: 0, start: Pos::new(3, 0), dir: Direction8::Left },
+                Match { word: 0, start: Pos::new(0, 3), dir: Direction8::Up },
+                Match { word: 0, start: Pos::new(0, 3), dir: Direction8::Right },
+            ]
+        );
+        assert_eq!(WordSearch::new(&map).count(&["XMAS", "Q"]), 3);
+    }
+
+    #[test]
+    fn palindromes_count_once() {
+        let map: Map = map("ABA\nBXB\nABA\n");
+        assert_eq!(WordSearch::new(&map).count(&["ABA"]), 4);
+        assert_eq!(WordSearch::new(&map).count(&["X"]), 1);
+        // ? Distinct words that read as each other are still both reported
+        assert_eq!(WordSearch::new(&map).count(&["AB", "BA"]), 8 + 8);
+    }
+
+    #[test]
+    fn templates_expand_to_every_orientation() {
+        let x_mas: Template = Template::parse("M.S / .A. / M.S").unwrap();
+        assert_eq!(x_mas.variants().len(), 4);
+        let corner: Template = Template::parse("AB\nC.").unwrap();
+        assert_eq!(corner.variants().len(), 8);
+        assert_eq!(corner.rotate(), Template::parse("CA/.B").unwrap());
+        assert_eq!(corner.reflect(), Template::parse("BA/.C").unwrap());
+        let bar: Template = Template::parse("XY.").unwrap();
+        assert_eq!((bar.rotate().width(), bar.rotate().height()), (1, 3));
+        assert!(Template::parse("AB/C").is_err());
+        assert!(Template::parse(" / ").is_err());
+    }
+
+    #[test]
+    fn templates_report_anchors() {
+        let map: Map = map("SXMQ\nXAQQ\nSQMQ\nQQQQ\n");
+        let x_mas: Template = Template::parse("M.S/.A./M.S").unwrap();
+        assert_eq!(x_mas.matches(&map), vec![Placement { anchor: Pos::new(0, 0), variant: 2 }]);
+        let bar: Template = Template::parse("XA.").unwrap();
+        assert_eq!(
+            bar.matches(&map),
+            vec![Placement { anchor: Pos::new(1, 0), variant: 1 }, Placement { anchor: Pos::new(0, 1), variant: 0 }]
+        );
+    }
+
+    #[test]
+    fn orientations_sharing_a_corner_count_apart() {
+        let square: Map = map("AA\nAA\n");
+        let map: Map = map("AB\nBC\n");
+        let pair: Template = Template::parse("AB").unwrap();
+        assert_eq!(
+            pair.matches(&map),
+            vec![Placement { anchor: Pos::new(0, 0), variant: 0 }, Placement { anchor: Pos::new(0, 0), variant: 1 }]
+        );
+        let bar: Template = Template::parse("A.").unwrap();
+        assert_eq!(bar.count(&map), 2);
+        assert_eq!(bar.count(&square), 8);
+    }
+
+    #[test]
+    fn wraps_around_the_edges() {
+        let map: Map = map("ASXM\nQQQQ\n");
+        assert_eq!(WordSearch::new(&map).count(&["XMAS"]), 0);
+        assert_eq!(
+            WordSearch::new(&map).with_wrap(true).find(&["XMAS"]),
+            vec![Match { word: 0, start: Pos::new(2, 0), dir: Direction8::Right }]
+        );
+    }
+}
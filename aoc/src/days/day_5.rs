@@ -0,0 +1,373 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use crate::error::{parse_token, ParseError};
+use crate::solution::Solution;
+
+pub type Update = Vec<i32>;
+
+/// `pred|succ`: when both pages are in an update, `pred` has to come first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    pub pred: i32,
+    pub succ: i32,
+}
+impl Rule {
+    pub fn new(pred: i32, succ: i32) -> Rule {
+        Rule { pred, succ }
+    }
+}
+
+/// Pages of an update that the rules order in a loop, each one before the next and the last one
+/// before the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    pub pages: Vec<i32>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the rules order pages in a cycle: ")?;
+        for page in &self.pages {
+            write!(f, "{} -> ", page)?;
+        }
+        write!(f, "{}", self.pages[0])
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// The rules as a precedence graph, from each page to the pages that have to follow it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleSet {
+    succs: HashMap<i32, HashSet<i32>>,
+}
+
+impl RuleSet {
+    pub fn new(rules: &[Rule]) -> RuleSet {
+        let mut succs: HashMap<i32, HashSet<i32>> = HashMap::new();
+        for rule in rules {
+            succs.entry(rule.pred).or_default().insert(rule.succ);
+        }
+        RuleSet { succs }
+    }
+
+    pub fn must_precede(&self, pred: i32, succ: i32) -> bool {
+        self.succs.get(&pred).is_some_and(|succs| succs.contains(&succ))
+    }
+
+    /// Whether no page of `update` comes after a page it has to precede, in time linear in the
+    /// update and the rules leaving its pages.
+    pub fn is_ordered(&self, update: &[i32]) -> bool {
+        let position: HashMap<i32, usize> = update.iter().enumerate().map(|(idx, page)| (*page, idx)).collect();
+        update.iter().enumerate().all(|(idx, page)| {
+            self.succs
+                .get(page)
+                .is_none_or(|succs| succs.iter().all(|succ| position.get(succ).is_none_or(|pos| *pos > idx)))
+        })
+    }
+
+    /// The pages of `update` reordered to follow every rule between them, ignoring rules about pages
+    /// it doesn't contain. Pages the rules leave free keep their relative order.
+    pub fn sort(&self, update: &[i32]) -> Result<Update, CycleError> {
+        let position: HashMap<i32, usize> = update.iter().enumerate().map(|(idx, page)| (*page, idx)).collect();
+        let mut succs: Vec<Vec<usize>> = vec![Vec::new(); update.len()];
+        let mut preds: Vec<Vec<usize>> = vec![Vec::new(); update.len()];
+        for (idx, page) in update.iter().enumerate() {
+            for succ in self.succs.get(page).into_iter().flatten() {
+                if let Some(&succ_idx) = position.get(succ) {
+                    succs[idx].push(succ_idx);
+                    preds[succ_idx].push(idx);
+                }
+            }
+        }
+
+        // ? Kahn's algorithm, always taking the earliest ready page so the result is stable
+        let mut pending: Vec<usize> = preds.iter().map(Vec::len).collect();
+        let mut ready: BTreeSet<usize> = (0..update.len()).filter(|idx| pending[*idx] == 0).collect();
+        let mut sorted: Update = Vec::with_capacity(update.len());
+        while let Some(idx) = ready.pop_first() {
+            sorted.push(update[idx]);
+            for succ in &succs[idx] {
+                pending[*succ] -= 1;
+                if pending[*succ] == 0 {
+                    ready.insert(*succ);
+                }
+            }
+        }
+        if sorted.len() == update.len() {
+            return Ok(sorted);
+        }
+
+        // ? Every page left has a predecessor left too, so walking back through them has to loop
+        let mut walk: Vec<usize> = vec![(0..update.len()).find(|idx| pending[*idx] > 0).unwrap()];
+        loop {
+            let last: usize = *walk.last().unwrap();
+            let pred: usize = *preds[last].iter().find(|pred| pending[**pred] > 0).unwrap();
+            if let Some(start) = walk.iter().position(|idx| *idx == pred) {
+                let pages: Vec<i32> = walk[start..].iter().rev().map(|idx| update[*idx]).collect();
+                return Err(CycleError { pages });
+            }
+            walk.push(pred);
+        }
+    }
+}
+
+pub fn load_data(data: &str) -> Result<(Vec<Rule>, Vec<Update>), ParseError> {
+    let mut lines = data.lines().enumerate();
+    let mut rules: Vec<Rule> = Vec::new();
+    let mut updates: Vec<Update> = Vec::new();
+    for (idx, line) in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+        let Some((pred, succ)) = line.split_once('|') else {
+            return Err(ParseError::at(idx, line, line, "expected a rule like `47|53`"));
+        };
+        let pred: i32 = parse_token(idx, line, pred)?;
+        let succ: i32 = parse_token(idx, line, succ)?;
+        rules.push(Rule::new(pred, succ));
+    }
+    for (idx, line) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let update: Update = line
+            .split(',')
+            .map(|x| parse_token::<i32>(idx, line, x))
+            .collect::<Result<Update, ParseError>>()?;
+        updates.push(update);
+    }
+    Ok((rules, updates))
+}
+
+/// What may be wrong with a set of rules and the updates they order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Analysis {
+    pub rules: usize,
+    /// Rules given more than once, with how many times.
+    pub duplicates: Vec<(Rule, usize)>,
+    /// Pairs of pages each required to come before the other, smallest page first.
+    pub contradictions: Vec<(i32, i32)>,
+    /// Groups of pages the rules order in a loop when taken all together, each sorted.
+    pub cycles: Vec<Vec<i32>>,
+    /// Updates, by index, that no order satisfies.
+    pub unorderable: Vec<(usize, CycleError)>,
+    /// Pages that appear in updates but in no rule.
+    pub unruled: Vec<i32>,
+}
+
+impl Analysis {
+    /// Whether every update can be ordered. A cycle over the whole rule set is fine as long as no
+    /// update holds all of it, which is how the puzzle inputs are built.
+    pub fn is_sound(&self) -> bool {
+        self.contradictions.is_empty() && self.unorderable.is_empty()
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} rules: {} duplicated, {} contradictions, {} cycles, {} unorderable updates, {} pages in no rule",
+            self.rules,
+            self.duplicates.len(),
+            self.contradictions.len(),
+            self.cycles.len(),
+            self.unorderable.len(),
+            self.unruled.len()
+        )?;
+        for (rule, count) in &self.duplicates {
+            writeln!(f, "duplicate: {}|{} given {} times", rule.pred, rule.succ, count)?;
+        }
+        for (a, b) in &self.contradictions {
+            writeln!(f, "contradiction: {}|{} and {}|{}", a, b, b, a)?;
+        }
+        for cycle in &self.cycles {
+            let pages: Vec<String> = cycle.iter().map(i32::to_string).collect();
+            writeln!(f, "cycle of {} pages: {}", cycle.len(), pages.join(", "))?;
+        }
+        for (idx, err) in &self.unorderable {
+            writeln!(f, "update {} cannot be ordered, {}", idx + 1, err)?;
+        }
+        for page in &self.unruled {
+            writeln!(f, "page {} appears in updates but in no rule", page)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check the rules for duplicates, contradictions and cycles, and the updates for pages no rule
+/// mentions or that cannot be ordered at all.
+pub fn analyze(rules: &[Rule], updates: &[Update]) -> Analysis {
+    let mut counts: BTreeMap<(i32, i32), usize> = BTreeMap::new();
+    for rule in rules {
+        *counts.entry((rule.pred, rule.succ)).or_insert(0) += 1;
+    }
+    let duplicates: Vec<(Rule, usize)> = counts
+        .iter()
+        .filter(|(_, count)| **count > 1)
+        .map(|((pred, succ), count)| (Rule::new(*pred, *succ), *count))
+        .collect();
+    let contradictions: Vec<(i32, i32)> = counts
+        .keys()
+        .filter(|(pred, succ)| pred < succ && counts.contains_key(&(*succ, *pred)))
+        .copied()
+        .collect();
+
+    let mut graph: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+    for (pred, succ) in counts.keys() {
+        graph.entry(*pred).or_default().push(*succ);
+        graph.entry(*succ).or_default();
+    }
+    let cycles: Vec<Vec<i32>> = strongly_connected(&graph)
+        .into_iter()
+        .filter(|group| group.len() > 1 || counts.contains_key(&(group[0], group[0])))
+        .collect();
+
+    let set: RuleSet = RuleSet::new(rules);
+    let unorderable: Vec<(usize, CycleError)> =
+        updates.iter().enumerate().filter_map(|(idx, update)| set.sort(update).err().map(|err| (idx, err))).collect();
+    let unruled: BTreeSet<i32> = updates.iter().flatten().filter(|page| !graph.contains_key(page)).copied().collect();
+
+    Analysis {
+        rules: rules.len(),
+        duplicates,
+        contradictions,
+        cycles,
+        unorderable,
+        unruled: unruled.into_iter().collect(),
+    }
+}
+
+// ? Tarjan's algorithm, with an explicit stack so long chains of rules can't overflow the real one.
+// ? Each component comes out sorted, and the components in order of their smallest page.
+fn strongly_connected(graph: &BTreeMap<i32, Vec<i32>>) -> Vec<Vec<i32>> {
+    let mut index: HashMap<i32, usize> = HashMap::new();
+    let mut low: HashMap<i32, usize> = HashMap::new();
+    let mut stack: Vec<i32> = Vec::new();
+    let mut on_stack: HashSet<i32> = HashSet::new();
+    let mut groups: Vec<Vec<i32>> = Vec::new();
+    for root in graph.keys() {
+        if index.contains_key(root) {
+            continue;
+        }
+        let mut frames: Vec<(i32, usize)> = vec![(*root, 0)];
+        while let Some((page, next)) = frames.pop() {
+            if next == 0 {
+                index.insert(page, index.len());
+                low.insert(page, index[&page]);
+                stack.push(page);
+                on_stack.insert(page);
+            }
+            if let Some(succ) = graph[&page].get(next) {
+                frames.push((page, next + 1));
+                if !index.contains_key(succ) {
+                    frames.push((*succ, 0));
+                } else if on_stack.contains(succ) {
+                    low.insert(page, low[&page].min(index[succ]));
+                }
+                continue;
+            }
+            if let Some((parent, _)) = frames.last() {
+                low.insert(*parent, low[parent].min(low[&page]));
+            }
+            if low[&page] == index[&page] {
+                let mut group: Vec<i32> = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack.remove(&member);
+                    group.push(member);
+                    if member == page {
+                        break;
+                    }
+                }
+                group.sort_unstable();
+                groups.push(group);
+            }
+        }
+    }
+    groups.sort_unstable();
+    groups
+}
+
+fn middle(update: &[i32]) -> i32 {
+    update[(update.len() - 1) / 2]
+}
+
+pub fn part_one(rules: &RuleSet, updates: &[Update]) -> i32 {
+    updates.iter().filter(|update| rules.is_ordered(update)).map(|update| middle(update)).sum()
+}
+
+pub fn part_two(rules: &RuleSet, updates: &[Update]) -> Result<i32, CycleError> {
+    let mut count: i32 = 0;
+    for update in updates.iter().filter(|update| !rules.is_ordered(update)) {
+        count += middle(&rules.sort(update)?);
+    }
+    Ok(count)
+}
+
+pub struct Day5;
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = (RuleSet, Vec<Update>);
+    type PartOne = i32;
+    type PartTwo = Result<i32, CycleError>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (rules, updates) = load_data(input)?;
+        Ok((RuleSet::new(&rules), updates))
+    }
+    fn part_one((rules, updates): &Self::Input) -> i32 {
+        part_one(rules, updates)
+    }
+    fn part_two((rules, updates): &Self::Input) -> Result<i32, CycleError> {
+        part_two(rules, updates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(rules: &[(i32, i32)]) -> RuleSet {
+        RuleSet::new(&rules.iter().map(|(pred, succ)| Rule::new(*pred, *succ)).collect::<Vec<Rule>>())
+    }
+
+    #[test]
+    fn validates_and_sorts_within_the_update() {
+        let rules: RuleSet = rules(&[(1, 2), (2, 3), (3, 4), (4, 1), (5, 2)]);
+        assert!(rules.is_ordered(&[1, 2, 3]));
+        assert!(rules.is_ordered(&[7, 5, 8, 2]));
+        assert!(!rules.is_ordered(&[2, 5]));
+        // ? The cycle only matters when the update holds all of it
+        assert_eq!(rules.sort(&[3, 9, 2, 1]), Ok(vec![9, 1, 2, 3]));
+        assert_eq!(rules.sort(&[2, 8, 5]), Ok(vec![8, 5, 2]));
+    }
+
+    #[test]
+    fn names_the_pages_of_a_cycle() {
+        let rules: RuleSet = rules(&[(1, 2), (2, 3), (3, 4), (4, 1), (5, 2)]);
+        let err: CycleError = rules.sort(&[5, 4, 3, 2, 1]).unwrap_err();
+        let mut pages: Vec<i32> = err.pages.clone();
+        pages.sort_unstable();
+        assert_eq!(pages, vec![1, 2, 3, 4]);
+        for (idx, page) in err.pages.iter().enumerate() {
+            assert!(rules.must_precede(*page, err.pages[(idx + 1) % err.pages.len()]));
+        }
+    }
+
+    #[test]
+    fn analysis_reports_every_problem() {
+        let (rules, updates) = load_data("1|2\n2|3\n3|1\n1|2\n4|5\n5|4\n6|6\n7|8\n\n1,2,9\n3,1,2\n4,5\n").unwrap();
+        let analysis: Analysis = analyze(&rules, &updates);
+        assert_eq!(analysis.duplicates, vec![(Rule::new(1, 2), 2)]);
+        assert_eq!(analysis.contradictions, vec![(4, 5)]);
+        assert_eq!(analysis.cycles, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+        assert_eq!(analysis.unorderable.iter().map(|(idx, _)| *idx).collect::<Vec<usize>>(), vec![1, 2]);
+        assert_eq!(analysis.unruled, vec![9]);
+        assert!(!analysis.is_sound());
+
+        let (rules, updates) = load_data("1|2\n2|3\n3|1\n\n1,2\n2,3\n").unwrap();
+        assert!(analyze(&rules, &updates).is_sound());
+    }
+}
@@ -0,0 +1,860 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use crate::error::ParseError;
+use crate::geometry::{Direction, Direction4, Pos};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Blank,
+    Wall,
+    Guard(Direction4),
+    /// `U`, `R`, `D` or `L`: only enterable moving that way, a wall otherwise.
+    OneWay(Direction4),
+    /// `0` to `9`: stepping on one moves the guard to the other tile with the same digit.
+    Teleporter(u8),
+}
+
+impl Tile {
+    pub fn from_char(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Blank),
+            '#' => Some(Tile::Wall),
+            '0'..='9' => Some(Tile::Teleporter(c as u8 - b'0')),
+            _ => Direction4::from_arrow(c).map(Tile::Guard).or_else(|| Direction4::from_letter(c).map(Tile::OneWay)),
+        }
+    }
+    pub fn to_char(&self) -> char {
+        match self {
+            Tile::Blank => '.',
+            Tile::Wall => '#',
+            Tile::Guard(dir) => dir.to_arrow(),
+            Tile::OneWay(Direction4::Up) => 'U',
+            Tile::OneWay(Direction4::Right) => 'R',
+            Tile::OneWay(Direction4::Down) => 'D',
+            Tile::OneWay(Direction4::Left) => 'L',
+            Tile::Teleporter(id) => (b'0' + id) as char,
+        }
+    }
+
+    /// Whether a guard moving towards `dir` has to turn instead of walking onto this tile.
+    pub fn blocks(&self, dir: Direction4) -> bool {
+        match self {
+            Tile::Wall => true,
+            Tile::OneWay(way) => *way != dir,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+pub type Map = Grid<Tile>;
+pub fn pretty_print(map: &Map) {
+    print!("{}", map);
+}
+
+/// The puzzle's map: blanks, walls and exactly one guard. Everything walking it only knows those.
+pub fn load_data(input: &str) -> Result<Map, ParseError> {
+    let map: Map = Grid::parse(input, |c| Tile::from_char(c).filter(|tile| matches!(tile, Tile::Blank | Tile::Wall | Tile::Guard(_))))?;
+    let guards: Vec<Pos> = map.iter().filter(|(_, tile)| matches!(tile, Tile::Guard(_))).map(|(pos, _)| pos).collect();
+    match guards[..] {
+        [] => Err(ParseError::end_of_input(map.height(), "no guard on the map")),
+        [_] => Ok(map),
+        [_, pos, ..] => Err(ParseError::new(pos.y + 1, pos.x + 1, &map[pos].to_string(), "the puzzle has a single guard")),
+    }
+}
+
+/// A map for `Simulation`, with the whole tile set and any number of guards, at least one.
+pub fn load_variant(input: &str) -> Result<Map, ParseError> {
+    let map: Map = Grid::parse(input, Tile::from_char)?;
+    if map.find(|tile| matches!(tile, Tile::Guard(_))).is_none() {
+        return Err(ParseError::end_of_input(map.height(), "no guard on the map"));
+    }
+    Ok(map)
+}
+
+// ? Where the guard starts and which way it is facing.
+fn find_guard(map: &Map) -> (Pos, Direction4) {
+    let guard_pos: Pos = map.find(|tile| matches!(tile, Tile::Guard(_))).unwrap();
+    match map[guard_pos] {
+        Tile::Guard(dir) => (guard_pos, dir),
+        _ => panic!("Invalid guard position"),
+    }
+}
+
+pub fn part_one(map: &Map) -> usize {
+    let mut visited: Grid<bool> = Grid::new(map.width(), map.height(), false);
+    // ? Set the current guard location as visited
+    let (mut guard_pos, mut guard_dir) = find_guard(map);
+    visited[guard_pos] = true;
+
+    while let Some(next_pos) = map.step(guard_pos, guard_dir) {
+        match map[next_pos] {
+            Tile::Wall => {
+                guard_dir = guard_dir.turn_right();
+                // ? We don't want to mark the wall as visited
+            }
+            _ => {
+                visited[next_pos] = true;
+                guard_pos = next_pos;
+            }
+        }
+    }
+
+    visited.positions_of(&true).count()
+}
+
+/// A dense set of guard states, one bit per cell and direction.
+#[derive(Debug, Clone)]
+pub struct StateSet {
+    width: usize,
+    bits: Vec<u64>,
+}
+
+impl StateSet {
+    pub fn new(map: &Map) -> StateSet {
+        StateSet { width: map.width(), bits: vec![0; (map.width() * map.height() * 4).div_ceil(64)] }
+    }
+
+    /// Whether the state is new, remembering it.
+    pub fn insert(&mut self, pos: Pos, dir: Direction4) -> bool {
+        let idx: usize = (pos.y * self.width + pos.x) * 4 + dir as usize;
+        let (word, bit) = (idx / 64, 1u64 << (idx % 64));
+        let new: bool = self.bits[word] & bit == 0;
+        self.bits[word] |= bit;
+        new
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+}
+
+// ? Marks a jump that runs off the map instead of stopping at a wall.
+const EXIT: u32 = u32::MAX;
+
+/// For every cell and direction, the last cell the guard reaches walking straight before a wall,
+/// or `EXIT` when nothing stops it before the edge.
+#[derive(Debug, Clone)]
+pub struct JumpTable {
+    width: usize,
+    stops: [Vec<u32>; 4],
+}
+
+impl JumpTable {
+    pub fn new(map: &Map) -> JumpTable {
+        let width: usize = map.width();
+        let stops: [Vec<u32>; 4] = Direction4::ALL.map(|dir| {
+            let mut stops: Vec<u32> = vec![EXIT; width * map.height()];
+            // ? Fill each cell after the one it walks into: row by row going up or left, backwards otherwise
+            let mut order: Vec<Pos> = map.positions().collect();
+            if matches!(dir, Direction4::Down | Direction4::Right) {
+                order.reverse();
+            }
+            for pos in order {
+                stops[pos.y * width + pos.x] = match map.step(pos, dir) {
+                    None => EXIT,
+                    Some(next) if matches!(map[next], Tile::Wall) => (pos.y * width + pos.x) as u32,
+                    Some(next) => stops[next.y * width + next.x],
+                };
+            }
+            stops
+        });
+        JumpTable { width, stops }
+    }
+
+    pub fn stop(&self, pos: Pos, dir: Direction4) -> Option<Pos> {
+        match self.stops[dir as usize][pos.y * self.width + pos.x] {
+            EXIT => None,
+            idx => Some(Pos::new(idx as usize % self.width, idx as usize / self.width)),
+        }
+    }
+}
+
+// ? How many steps from `pos` towards `dir` reach `target`, if it lies straight ahead.
+fn steps_to(pos: Pos, dir: Direction4, target: Pos) -> Option<usize> {
+    match dir {
+        Direction4::Up if target.x == pos.x && target.y < pos.y => Some(pos.y - target.y),
+        Direction4::Down if target.x == pos.x && target.y > pos.y => Some(target.y - pos.y),
+        Direction4::Left if target.y == pos.y && target.x < pos.x => Some(pos.x - target.x),
+        Direction4::Right if target.y == pos.y && target.x > pos.x => Some(target.x - pos.x),
+        _ => None,
+    }
+}
+
+/// Finds where a single extra obstruction traps the guard in a loop.
+///
+/// Only cells of the original patrol can change it, and the patrol is the same up to the first time
+/// the guard would walk into the obstruction, so each candidate is simulated from that moment on.
+pub struct LoopFinder<'a> {
+    map: &'a Map,
+    jumps: Option<JumpTable>,
+}
+
+impl<'a> LoopFinder<'a> {
+    pub fn new(map: &'a Map) -> LoopFinder<'a> {
+        LoopFinder { map, jumps: None }
+    }
+
+    /// Walk straight from wall to wall with precomputed jumps, so each simulation costs one lookup
+    /// per turn instead of one per step.
+    pub fn with_jumps(mut self) -> LoopFinder<'a> {
+        self.jumps = Some(JumpTable::new(self.map));
+        self
+    }
+
+    /// Every cell the guard walks into, except the start, with the state right before it first does.
+    pub fn candidates(&self) -> Vec<(Pos, Pos, Direction4)> {
+        let mut seen: Grid<bool> = Grid::new(self.map.width(), self.map.height(), false);
+        let mut states: StateSet = StateSet::new(self.map);
+        let (mut guard_pos, mut guard_dir) = find_guard(self.map);
+        seen[guard_pos] = true;
+        let mut candidates: Vec<(Pos, Pos, Direction4)> = Vec::new();
+        // ? Stop should the patrol already loop without any obstruction
+        while let Some(next_pos) = self.map.step(guard_pos, guard_dir).filter(|_| states.insert(guard_pos, guard_dir)) {
+            if matches!(self.map[next_pos], Tile::Wall) {
+                guard_dir = guard_dir.turn_right();
+                continue;
+            }
+            if !seen[next_pos] {
+                seen[next_pos] = true;
+                candidates.push((next_pos, guard_pos, guard_dir));
+            }
+            guard_pos = next_pos;
+        }
+        candidates
+    }
+
+    /// Whether the guard, at `pos` facing `dir`, ends up in a loop once `obstruction` is placed.
+    pub fn loops(&self, obstruction: Pos, pos: Pos, dir: Direction4, visited: &mut StateSet) -> bool {
+        visited.clear();
+        match &self.jumps {
+            Some(jumps) => self.loops_jumping(jumps, obstruction, pos, dir, visited),
+            None => self.loops_stepping(obstruction, pos, dir, visited),
+        }
+    }
+
+    fn loops_stepping(&self, obstruction: Pos, mut pos: Pos, mut dir: Direction4, visited: &mut StateSet) -> bool {
+        while visited.insert(pos, dir) {
+            let Some(next_pos) = self.map.step(pos, dir) else {
+                return false;
+            };
+            if next_pos == obstruction || matches!(self.map[next_pos], Tile::Wall) {
+                dir = dir.turn_right();
+            } else {
+                pos = next_pos;
+            }
+        }
+        true
+    }
+
+    // ? Only the states right after a turn are recorded, a loop has to turn anyway
+    fn loops_jumping(&self, jumps: &JumpTable, obstruction: Pos, mut pos: Pos, mut dir: Direction4, visited: &mut StateSet) -> bool {
+        loop {
+            let stop: Option<Pos> = jumps.stop(pos, dir);
+            // ? The obstruction stops the guard first when it is closer than the wall, or than the edge
+            let blocked: Option<usize> = steps_to(pos, dir, obstruction)
+                .filter(|steps| stop.is_none_or(|stop| *steps <= steps_to(pos, dir, stop).unwrap_or(0)));
+            pos = match (blocked, stop) {
+                (Some(steps), _) => pos.offset(dir.delta().0 * (steps as isize - 1), dir.delta().1 * (steps as isize - 1)).unwrap(),
+                (None, Some(stop)) => stop,
+                (None, None) => return false,
+            };
+            dir = dir.turn_right();
+            if !visited.insert(pos, dir) {
+                return true;
+            }
+        }
+    }
+
+    /// Every position where an obstruction makes the guard loop, row by row.
+    pub fn obstructions(&self) -> Vec<Pos> {
+        let mut visited: StateSet = StateSet::new(self.map);
+        let mut found: Vec<Pos> = self
+            .candidates()
+            .into_iter()
+            .filter(|(obstruction, pos, dir)| self.loops(*obstruction, *pos, *dir, &mut visited))
+            .map(|(obstruction, _, _)| obstruction)
+            .collect();
+        found.sort_unstable();
+        found
+    }
+
+    /// `obstructions` with the candidates spread over rayon's thread pool, giving the same result.
+    #[cfg(feature = "parallel")]
+    pub fn par_obstructions(&self) -> Vec<Pos> {
+        use rayon::prelude::*;
+
+        let mut found: Vec<Pos> = self
+            .candidates()
+            .into_par_iter()
+            .map_init(|| StateSet::new(self.map), |visited, (obstruction, pos, dir)| self.loops(obstruction, pos, dir, visited).then_some(obstruction))
+            .flatten()
+            .collect();
+        found.sort_unstable();
+        found
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn part_two(map: &Map) -> usize {
+    LoopFinder::new(map).with_jumps().obstructions().len()
+}
+
+#[cfg(feature = "parallel")]
+pub fn part_two(map: &Map) -> usize {
+    LoopFinder::new(map).with_jumps().par_obstructions().len()
+}
+
+/// One move of the guard: a step into the next cell, or a turn in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Step { to: Pos },
+    Turn { to: Direction4 },
+}
+
+/// The whole patrol of the guard, event by event, until it leaves the map or starts looping.
+#[derive(Debug, Clone)]
+pub struct PatrolTrace {
+    map: Map,
+    start: (Pos, Direction4),
+    events: Vec<Event>,
+    looped: bool,
+}
+
+// ? What a frame shows in a cell, also the palette index of its color in animations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Blank,
+    Wall,
+    Vertical,
+    Horizontal,
+    Both,
+    Guard(Direction4),
+    Obstruction,
+}
+
+impl Mark {
+    fn to_char(self) -> char {
+        match self {
+            Mark::Blank => '.',
+            Mark::Wall => '#',
+            Mark::Vertical => '|',
+            Mark::Horizontal => '-',
+            Mark::Both => '+',
+            Mark::Guard(dir) => dir.to_arrow(),
+            Mark::Obstruction => 'O',
+        }
+    }
+
+    fn color(self) -> u8 {
+        match self {
+            Mark::Blank => 0,
+            Mark::Wall => 1,
+            Mark::Vertical | Mark::Horizontal | Mark::Both => 2,
+            Mark::Guard(_) => 3,
+            Mark::Obstruction => 4,
+        }
+    }
+}
+
+// ? Blank, wall, path, guard and obstruction colors, as RGB triples
+const PALETTE: [u8; 15] = [24, 24, 40, 130, 130, 140, 255, 204, 0, 230, 50, 50, 0, 200, 255];
+
+impl PatrolTrace {
+    pub fn record(map: &Map) -> PatrolTrace {
+        let start: (Pos, Direction4) = find_guard(map);
+        let (mut guard_pos, mut guard_dir) = start;
+        let mut states: StateSet = StateSet::new(map);
+        let mut events: Vec<Event> = Vec::new();
+        let mut looped: bool = false;
+        while let Some(next_pos) = map.step(guard_pos, guard_dir) {
+            if !states.insert(guard_pos, guard_dir) {
+                looped = true;
+                break;
+            }
+            if matches!(map[next_pos], Tile::Wall) {
+                guard_dir = guard_dir.turn_right();
+                events.push(Event::Turn { to: guard_dir });
+            } else {
+                guard_pos = next_pos;
+                events.push(Event::Step { to: next_pos });
+            }
+        }
+        PatrolTrace { map: map.clone(), start, events, looped }
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Whether the guard never leaves the map.
+    pub fn looped(&self) -> bool {
+        self.looped
+    }
+
+    /// How many distinct cells the guard stands on, the start included.
+    pub fn visited(&self) -> usize {
+        let mut seen: Grid<bool> = Grid::new(self.map.width(), self.map.height(), false);
+        seen[self.start.0] = true;
+        for event in &self.events {
+            if let Event::Step { to } = event {
+                seen[*to] = true;
+            }
+        }
+        seen.positions_of(&true).count()
+    }
+
+    // ? The map after the first `events` moves, with `overlay` on top of the blank cells
+    fn marks(&self, events: usize, overlay: &[Pos]) -> Grid<Mark> {
+        let mut replay: Replay = Replay::new(self);
+        replay.advance(events);
+        replay.marks(overlay)
+    }
+
+    /// The map after the first `events` moves, drawing the path with `|`, `-` and `+` like the puzzle
+    /// does, the guard with its arrow and every position of `overlay` as `O`.
+    pub fn render(&self, events: usize, overlay: &[Pos]) -> String {
+        to_text(&self.marks(events, overlay))
+    }
+
+    // ? How many events each frame shows, every `every` moves and always the final state
+    fn frame_ends(&self, every: usize) -> Vec<usize> {
+        let mut ends: Vec<usize> = (0..self.events.len()).step_by(every.max(1)).collect();
+        ends.push(self.events.len());
+        ends
+    }
+
+    // ? Replays the patrol once for all the frames instead of from the start for each of them
+    fn mark_frames<'a>(&'a self, every: usize, overlay: &'a [Pos]) -> impl Iterator<Item = Grid<Mark>> + 'a {
+        let mut replay: Replay = Replay::new(self);
+        self.frame_ends(every).into_iter().map(move |events| {
+            replay.advance(events);
+            replay.marks(overlay)
+        })
+    }
+
+    /// One rendering every `every` moves, from the start to the end of the patrol, drawn as the
+    /// iterator is advanced.
+    pub fn frames<'a>(&'a self, every: usize, overlay: &'a [Pos]) -> impl Iterator<Item = String> + 'a {
+        self.mark_frames(every, overlay).map(|marks| to_text(&marks))
+    }
+
+    /// Write the frames as an animated GIF, each cell `scale` pixels wide. Past the first frame only
+    /// the cells that changed are encoded, the rest stays on screen.
+    pub fn write_gif(&self, out: impl Write, every: usize, scale: usize, overlay: &[Pos]) -> io::Result<()> {
+        let scale: usize = scale.max(1);
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "the animation would be too large");
+        let mut encoder: gif::Encoder<_> = gif::Encoder::new(
+            out,
+            u16::try_from(self.map.width() * scale).map_err(|_| too_large())?,
+            u16::try_from(self.map.height() * scale).map_err(|_| too_large())?,
+            &PALETTE,
+        )
+        .map_err(io::Error::other)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+        let mut previous: Option<Grid<Mark>> = None;
+        for marks in self.mark_frames(every, overlay) {
+            let (first, last) = match &previous {
+                Some(previous) => changed_area(previous, &marks).unwrap_or((Pos::default(), Pos::default())),
+                None => (Pos::default(), Pos::new(marks.width() - 1, marks.height() - 1)),
+            };
+            let (width, height) = ((last.x - first.x + 1) * scale, (last.y - first.y + 1) * scale);
+            let mut pixels: Vec<u8> = Vec::with_capacity(width * height);
+            for y in first.y..=last.y {
+                let line: Vec<u8> = marks.row(y)[first.x..=last.x]
+                    .iter()
+                    .flat_map(|mark| std::iter::repeat_n(mark.color(), scale))
+                    .collect();
+                for _ in 0..scale {
+                    pixels.extend_from_slice(&line);
+                }
+            }
+            let mut frame: gif::Frame = gif::Frame::from_indexed_pixels(width as u16, height as u16, pixels, None);
+            frame.left = (first.x * scale) as u16;
+            frame.top = (first.y * scale) as u16;
+            frame.dispose = gif::DisposalMethod::Keep;
+            frame.delay = 4;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+            previous = Some(marks);
+        }
+        Ok(())
+    }
+}
+
+// ? The path drawn so far, advanced one event at a time
+struct Replay<'a> {
+    events: &'a [Event],
+    path: Grid<Mark>,
+    guard: (Pos, Direction4),
+    applied: usize,
+}
+
+impl<'a> Replay<'a> {
+    fn new(trace: &'a PatrolTrace) -> Replay<'a> {
+        let path: Grid<Mark> = trace.map.map(|tile| match tile {
+            Tile::Wall => Mark::Wall,
+            _ => Mark::Blank,
+        });
+        Replay { events: &trace.events, path, guard: trace.start, applied: 0 }
+    }
+
+    fn mark(&mut self, pos: Pos, new: Mark) {
+        self.path[pos] = match self.path[pos] {
+            Mark::Blank => new,
+            old if old == new => old,
+            _ => Mark::Both,
+        };
+    }
+
+    /// Draw the moves up to the first `events` ones, which can only grow between calls.
+    fn advance(&mut self, events: usize) {
+        let axis = |dir: Direction4| match dir {
+            Direction4::Up | Direction4::Down => Mark::Vertical,
+            Direction4::Left | Direction4::Right => Mark::Horizontal,
+        };
+        let end: usize = events.clamp(self.applied, self.events.len());
+        for idx in self.applied..end {
+            let (guard_pos, guard_dir) = self.guard;
+            match self.events[idx] {
+                Event::Step { to } => {
+                    self.mark(guard_pos, axis(guard_dir));
+                    self.mark(to, axis(guard_dir));
+                    self.guard.0 = to;
+                }
+                Event::Turn { to } => {
+                    self.mark(guard_pos, Mark::Both);
+                    self.guard.1 = to;
+                }
+            }
+        }
+        self.applied = end;
+    }
+
+    fn marks(&self, overlay: &[Pos]) -> Grid<Mark> {
+        let mut marks: Grid<Mark> = self.path.clone();
+        for pos in overlay {
+            if marks.contains(*pos) && !matches!(marks[*pos], Mark::Wall) {
+                marks[*pos] = Mark::Obstruction;
+            }
+        }
+        marks[self.guard.0] = Mark::Guard(self.guard.1);
+        marks
+    }
+}
+
+fn to_text(marks: &Grid<Mark>) -> String {
+    let mut out: String = String::with_capacity((marks.width() + 1) * marks.height());
+    for row in marks.rows() {
+        out.extend(row.iter().map(|mark| mark.to_char()));
+        out.push('\n');
+    }
+    out
+}
+
+// ? The corners of the smallest rectangle holding every cell that differs between the frames
+fn changed_area(previous: &Grid<Mark>, next: &Grid<Mark>) -> Option<(Pos, Pos)> {
+    next.iter()
+        .filter(|(pos, mark)| previous[*pos] != **mark)
+        .map(|(pos, _)| (pos, pos))
+        .reduce(|(first, last), (pos, _)| {
+            (Pos::new(first.x.min(pos.x), first.y.min(pos.y)), Pos::new(last.x.max(pos.x), last.y.max(pos.y)))
+        })
+}
+
+/// How a guard's patrol ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    LeavesMap,
+    Loops,
+}
+
+/// The patrol of one guard: where it started, how many distinct cells it stood on and after how
+/// many moves it left the map or came back to a state it had already been in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuardReport {
+    pub start: Pos,
+    pub dir: Direction4,
+    pub visited: usize,
+    pub moves: usize,
+    pub ending: Ending,
+}
+
+// ? One move of a guard in a simulation
+enum Move {
+    Turn(Direction4),
+    Step(Pos),
+    Teleport { pad: Pos, to: Pos },
+}
+
+// ? A guard while the simulation runs
+struct Guard {
+    pos: Pos,
+    dir: Direction4,
+    states: StateSet,
+    seen: Grid<bool>,
+    report: GuardReport,
+    ended: bool,
+}
+
+impl Guard {
+    fn visit(&mut self, pos: Pos) {
+        if !self.seen[pos] {
+            self.seen[pos] = true;
+            self.report.visited += 1;
+        }
+    }
+}
+
+/// Runs every guard of a map with the whole tile set: one-way tiles and teleporters on top of the
+/// puzzle's walls. The guards move in lockstep, one move each per tick, without blocking each other.
+pub struct Simulation<'a> {
+    map: &'a Map,
+    teleports: HashMap<Pos, Pos>,
+}
+
+impl<'a> Simulation<'a> {
+    /// Fails when a teleporter digit is not on exactly two tiles.
+    pub fn new(map: &'a Map) -> Result<Simulation<'a>, ParseError> {
+        let mut pads: BTreeMap<u8, Vec<Pos>> = BTreeMap::new();
+        for (pos, tile) in map.iter() {
+            if let Tile::Teleporter(id) = tile {
+                pads.entry(*id).or_default().push(pos);
+            }
+        }
+        let mut teleports: HashMap<Pos, Pos> = HashMap::new();
+        for (id, pads) in pads {
+            let [a, b] = pads[..] else {
+                let pos: Pos = pads[0];
+                let message: String = format!("teleporter {} is on {} tiles instead of a pair", id, pads.len());
+                return Err(ParseError::new(pos.y + 1, pos.x + 1, &id.to_string(), message));
+            };
+            teleports.insert(a, b);
+            teleports.insert(b, a);
+        }
+        Ok(Simulation { map, teleports })
+    }
+
+    /// Every guard on the map with the way it faces, row by row.
+    pub fn guards(&self) -> Vec<(Pos, Direction4)> {
+        self.map
+            .iter()
+            .filter_map(|(pos, tile)| match tile {
+                Tile::Guard(dir) => Some((pos, *dir)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // ? The next move of a guard at `pos` facing `dir`, `None` once it walks off the map
+    fn advance(&self, pos: Pos, dir: Direction4) -> Option<Move> {
+        let next_pos: Pos = self.map.step(pos, dir)?;
+        if self.map[next_pos].blocks(dir) {
+            return Some(Move::Turn(dir.turn_right()));
+        }
+        match self.teleports.get(&next_pos) {
+            Some(to) => Some(Move::Teleport { pad: next_pos, to: *to }),
+            None => Some(Move::Step(next_pos)),
+        }
+    }
+
+    /// Move every guard until each one has left the map or is looping, reporting them in the order
+    /// of `guards`.
+    pub fn run(&self) -> Vec<GuardReport> {
+        let mut guards: Vec<Guard> = self
+            .guards()
+            .into_iter()
+            .map(|(pos, dir)| {
+                let mut guard: Guard = Guard {
+                    pos,
+                    dir,
+                    states: StateSet::new(self.map),
+                    seen: Grid::new(self.map.width(), self.map.height(), false),
+                    report: GuardReport { start: pos, dir, visited: 0, moves: 0, ending: Ending::LeavesMap },
+                    ended: false,
+                };
+                guard.visit(pos);
+                guard
+            })
+            .collect();
+        while guards.iter().any(|guard| !guard.ended) {
+            for guard in guards.iter_mut().filter(|guard| !guard.ended) {
+                if !guard.states.insert(guard.pos, guard.dir) {
+                    guard.report.ending = Ending::Loops;
+                    guard.ended = true;
+                    continue;
+                }
+                match self.advance(guard.pos, guard.dir) {
+                    None => guard.ended = true,
+                    Some(Move::Turn(dir)) => guard.dir = dir,
+                    Some(Move::Step(pos)) => {
+                        guard.pos = pos;
+                        guard.visit(pos);
+                    }
+                    Some(Move::Teleport { pad, to }) => {
+                        guard.visit(pad);
+                        guard.pos = to;
+                        guard.visit(to);
+                    }
+                }
+                if !guard.ended {
+                    guard.report.moves += 1;
+                }
+            }
+        }
+        guards.into_iter().map(|guard| guard.report).collect()
+    }
+}
+
+pub struct Day6;
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Map;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_data(input)
+    }
+    fn part_one(map: &Self::Input) -> usize {
+        part_one(map)
+    }
+    fn part_two(map: &Self::Input) -> usize {
+        part_two(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::testing::Rng;
+
+    const EXAMPLE: &str = "....#.....\n.........#\n..........\n..#.......\n.......#..\n..........\n.#..^.....\n........#.\n#.........\n......#...\n";
+
+    // ? The whole patrol from the start, with a set of states
+    fn loops(map: &Map, obstruction: Option<Pos>) -> bool {
+        let mut states: HashSet<(Pos, Direction4)> = HashSet::new();
+        let (mut pos, mut dir) = find_guard(map);
+        while states.insert((pos, dir)) {
+            let Some(next_pos) = map.step(pos, dir) else {
+                return false;
+            };
+            if Some(next_pos) == obstruction || matches!(map[next_pos], Tile::Wall) {
+                dir = dir.turn_right();
+            } else {
+                pos = next_pos;
+            }
+        }
+        true
+    }
+
+    fn brute_force(map: &Map) -> Vec<Pos> {
+        map.positions().filter(|pos| matches!(map[*pos], Tile::Blank) && loops(map, Some(*pos))).collect()
+    }
+
+    #[test]
+    fn finds_the_example_obstructions() {
+        let map: Map = load_data(EXAMPLE).unwrap();
+        let expected: Vec<Pos> = vec![Pos::new(3, 6), Pos::new(6, 7), Pos::new(7, 7), Pos::new(1, 8), Pos::new(3, 8), Pos::new(7, 9)];
+        assert_eq!(LoopFinder::new(&map).obstructions(), expected);
+        assert_eq!(LoopFinder::new(&map).with_jumps().obstructions(), expected);
+        assert_eq!(LoopFinder::new(&map).candidates().len() + 1, part_one(&map));
+    }
+
+    #[test]
+    fn renders_the_patrol_like_the_puzzle() {
+        let map: Map = load_data(EXAMPLE).unwrap();
+        let trace: PatrolTrace = PatrolTrace::record(&map);
+        assert!(!trace.looped());
+        assert_eq!(trace.visited(), 41);
+        assert_eq!(trace.render(0, &[]), EXAMPLE);
+        let turns: usize = trace.events().iter().filter(|event| matches!(event, Event::Turn { .. })).count();
+        assert_eq!(turns, 10);
+        assert_eq!(
+            trace.render(trace.events().len(), &[Pos::new(3, 6)]),
+            "....#.....\n....+---+#\n....|...|.\n..#.|...|.\n..+-+-+#|.\n..|.|.|.|.\n.#+O+-+-+.\n.+----++#.\n#+----+|..\n......#v..\n"
+        );
+        assert_eq!(trace.frames(10, &[]).count(), trace.events().len().div_ceil(10) + 1);
+        let overlay: Vec<Pos> = vec![Pos::new(3, 6), Pos::new(7, 9)];
+        for (events, frame) in trace.frames(1, &overlay).enumerate() {
+            assert_eq!(frame, trace.render(events, &overlay));
+        }
+    }
+
+    #[test]
+    fn writes_an_animated_gif() {
+        let map: Map = load_data(EXAMPLE).unwrap();
+        let mut gif: Vec<u8> = Vec::new();
+        PatrolTrace::record(&map).write_gif(&mut gif, 5, 3, &[]).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(u16::from_le_bytes([gif[6], gif[7]]), 30);
+
+        // ? The first frame covers the map, the next ones only the cells the guard went through since
+        let mut decoder: gif::Decoder<&[u8]> = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        let mut areas: Vec<(u16, u16, u16, u16)> = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            areas.push((frame.left, frame.top, frame.width, frame.height));
+        }
+        assert_eq!(areas.len(), 12);
+        assert_eq!(areas[0], (0, 0, 30, 30));
+        assert_eq!(areas[1], (12, 3, 3, 18));
+    }
+
+    #[test]
+    fn simulates_the_puzzle_guard() {
+        let map: Map = load_data(EXAMPLE).unwrap();
+        let reports: Vec<GuardReport> = Simulation::new(&map).unwrap().run();
+        assert_eq!(reports.len(), 1);
+        assert_eq!((reports[0].visited, reports[0].moves, reports[0].ending), (41, 54, Ending::LeavesMap));
+    }
+
+    #[test]
+    fn guards_use_one_way_tiles_and_teleporters() {
+        // ? The first guard jumps over the wall, the second goes through the one-way tile then the
+        // ? teleporter, and the third bounces off a one-way tile into a loop
+        let map: Map = load_variant(">1#1.\n.U...\n.^...\n.#...\n.^.L.\n#....\n..#..\n").unwrap();
+        let reports: Vec<GuardReport> = Simulation::new(&map).unwrap().run();
+        assert_eq!(
+            reports,
+            vec![
+                GuardReport { start: Pos::new(0, 0), dir: Direction4::Right, visited: 4, moves: 2, ending: Ending::LeavesMap },
+                GuardReport { start: Pos::new(1, 2), dir: Direction4::Up, visited: 4, moves: 2, ending: Ending::LeavesMap },
+                GuardReport { start: Pos::new(1, 4), dir: Direction4::Up, visited: 4, moves: 8, ending: Ending::Loops },
+            ]
+        );
+        assert!(Simulation::new(&load_variant("^3.3.3\n").unwrap()).is_err());
+        assert!(Simulation::new(&load_variant("^.4\n").unwrap()).is_err());
+        // ? The puzzle's own walkers only know walls, so its parser turns the rest down
+        assert_eq!(load_data("^.5\n").unwrap_err().column, 3);
+        assert_eq!(load_data("^.D\n").unwrap_err().column, 3);
+        assert_eq!(load_data(".^\n.<\n").unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn matches_brute_force_on_random_maps() {
+        let mut rng: Rng = Rng::new(0x9e3779b97f4a7c15);
+        for _ in 0..200 {
+            let (width, height) = (3 + rng.below(10) as usize, 3 + rng.below(10) as usize);
+            let mut cells: Vec<Tile> = (0..width * height).map(|_| if rng.below(6) == 0 { Tile::Wall } else { Tile::Blank }).collect();
+            cells[rng.below((width * height) as u64) as usize] = Tile::Guard(Direction4::ALL[rng.below(4) as usize]);
+            let map: Map = Grid::from_vec(width, height, cells).unwrap();
+            if loops(&map, None) {
+                continue;
+            }
+            let expected: Vec<Pos> = brute_force(&map);
+            assert_eq!(LoopFinder::new(&map).obstructions(), expected, "{}", map);
+            assert_eq!(LoopFinder::new(&map).with_jumps().obstructions(), expected, "{}", map);
+            #[cfg(feature = "parallel")]
+            assert_eq!(LoopFinder::new(&map).with_jumps().par_obstructions(), expected, "{}", map);
+        }
+    }
+}
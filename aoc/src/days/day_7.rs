@@ -0,0 +1,266 @@
+use crate::error::{parse_token, ParseError};
+use crate::solution::Solution;
+
+#[derive(Debug)]
+pub struct Equation {
+    pub result: u64,
+    pub factors: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Mul,
+    /// Append the digits of the right operand to the left one.
+    Concat,
+}
+
+impl Operator {
+    pub const PART_ONE: &[Operator] = &[Operator::Add, Operator::Mul];
+    pub const PART_TWO: &[Operator] = &[Operator::Add, Operator::Mul, Operator::Concat];
+
+    /// `a op b`, or `None` when it doesn't fit in a `u64`.
+    pub fn apply(self, a: u64, b: u64) -> Option<u64> {
+        match self {
+            Operator::Add => a.checked_add(b),
+            Operator::Mul => a.checked_mul(b),
+            Operator::Concat => match shift(b) {
+                Some(shift) => a.checked_mul(shift)?.checked_add(b),
+                // ? Leading zeros vanish, so only `0 || b` fits once `b` has 20 digits
+                None => (a == 0).then_some(b),
+            },
+        }
+    }
+
+    // ? The `a` such that `a op b == target`, if there is exactly one. A product by zero
+    // ? fits any `a` and is handled by the caller.
+    fn undo(self, target: u64, b: u64) -> Option<u64> {
+        match self {
+            Operator::Add => target.checked_sub(b),
+            Operator::Mul => (b != 0 && target.is_multiple_of(b)).then(|| target / b),
+            Operator::Concat => {
+                let rest: u64 = target.checked_sub(b)?;
+                match shift(b) {
+                    Some(shift) => rest.is_multiple_of(shift).then(|| rest / shift),
+                    None => (rest == 0).then_some(0),
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Mul => write!(f, "*"),
+            Operator::Concat => write!(f, "||"),
+        }
+    }
+}
+
+// ? The power of ten that makes room for the digits of `n`, which has at least one, if it fits.
+fn shift(n: u64) -> Option<u64> {
+    10u64.checked_pow(n.checked_ilog10().unwrap_or(0) + 1)
+}
+
+impl Equation {
+    /// Fold the factors left to right with `operators`, one fewer than the factors, `None` on overflow.
+    pub fn evaluate(&self, operators: &[Operator]) -> Option<u64> {
+        let (first, rest) = self.factors.split_first()?;
+        rest.iter().zip(operators).try_fold(*first, |value, (factor, op)| op.apply(value, *factor))
+    }
+
+    /// Operators, chosen among `operators`, that turn the factors into the result when folded left
+    /// to right. The search runs from the result backwards, undoing the last factor first: a
+    /// subtraction has to stay positive, a division has to be exact and a concatenation has to
+    /// leave the factor's digits behind, which prunes most branches right away.
+    pub fn solve(&self, operators: &[Operator]) -> Option<Vec<Operator>> {
+        let mut found: Vec<Operator> = Vec::with_capacity(self.factors.len().saturating_sub(1));
+        solve_backwards(self.result, &self.factors, operators, &mut found).then_some(found)
+    }
+}
+
+// ? Whether `factors` can make `target`, pushing the operators in order onto `found` when they can.
+fn solve_backwards(target: u64, factors: &[u64], operators: &[Operator], found: &mut Vec<Operator>) -> bool {
+    let Some((last, rest)) = factors.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return target == *last;
+    }
+    for op in operators {
+        let solved: bool = match op.undo(target, *last) {
+            Some(prev) => solve_backwards(prev, rest, operators, found),
+            // ? Anything times zero is zero, so the rest only has to fold without overflowing
+            None if *op == Operator::Mul && *last == 0 && target == 0 => match smallest_fold(rest, operators) {
+                Some(ops) => {
+                    found.extend(ops);
+                    true
+                }
+                None => false,
+            },
+            None => false,
+        };
+        if solved {
+            found.push(*op);
+            return true;
+        }
+    }
+    false
+}
+
+// ? Operators folding `factors` into the smallest value they can reach, `None` if every fold overflows.
+// ? Each operator grows with its left operand, so keeping the smallest value at every step is never
+// ? worse than any other choice.
+fn smallest_fold(factors: &[u64], operators: &[Operator]) -> Option<Vec<Operator>> {
+    let (first, rest) = factors.split_first()?;
+    let mut value: u64 = *first;
+    let mut ops: Vec<Operator> = Vec::with_capacity(rest.len());
+    for factor in rest {
+        let (next, op) = operators.iter().filter_map(|op| Some((op.apply(value, *factor)?, *op))).min_by_key(|(next, _)| *next)?;
+        value = next;
+        ops.push(op);
+    }
+    Some(ops)
+}
+
+pub fn load_data(input: &str) -> Result<Vec<Equation>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let Some((result, factors)) = line.split_once(':') else {
+                return Err(ParseError::at(idx, line, line, "expected an equation like `190: 10 19`"));
+            };
+            let result: u64 = parse_token(idx, line, result)?;
+            let factors: Vec<u64> = factors
+                .split_whitespace()
+                .map(|n| parse_token::<u64>(idx, line, n))
+                .collect::<Result<Vec<u64>, ParseError>>()?;
+            if factors.is_empty() {
+                return Err(ParseError::at(idx, line, &line[line.len()..], "expected at least one factor"));
+            }
+            Ok(Equation { result, factors })
+        })
+        .collect()
+}
+
+// ? Summed as u128: any number of u64 results fits, where a u64 total could overflow
+fn calibration(equations: &[Equation], operators: &[Operator]) -> u128 {
+    equations.iter().filter(|eq| eq.solve(operators).is_some()).map(|eq| eq.result as u128).sum()
+}
+
+pub fn part_one(equations: &[Equation]) -> u128 {
+    calibration(equations, Operator::PART_ONE)
+}
+
+pub fn part_two(equations: &[Equation]) -> u128 {
+    calibration(equations, Operator::PART_TWO)
+}
+
+pub struct Day7;
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = Vec<Equation>;
+    type PartOne = u128;
+    type PartTwo = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_data(input)
+    }
+    fn part_one(equations: &Self::Input) -> u128 {
+        part_one(equations)
+    }
+    fn part_two(equations: &Self::Input) -> u128 {
+        part_two(equations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Rng;
+
+    // ? The previous solver, with checked arithmetic: every left-to-right combination, with
+    // ? concatenation through strings. Nothing is pruned, a zero factor can bring any value back down.
+    fn forward(eq: &Equation, concat: bool) -> bool {
+        let mut possibles: Vec<u64> = vec![eq.factors[0]];
+        for curr in &eq.factors[1..] {
+            let mut temp: Vec<u64> = vec![];
+            for p in possibles {
+                temp.extend(p.checked_add(*curr));
+                temp.extend(p.checked_mul(*curr));
+                if concat {
+                    temp.extend(format!("{}{}", p, curr).parse::<u64>().ok());
+                }
+            }
+            possibles = temp;
+        }
+        possibles.contains(&eq.result)
+    }
+
+    #[test]
+    fn returns_the_operators() {
+        let eq: Equation = Equation { result: 292, factors: vec![11, 6, 16, 20] };
+        assert_eq!(eq.solve(Operator::PART_ONE), Some(vec![Operator::Add, Operator::Mul, Operator::Add]));
+        let eq: Equation = Equation { result: 7290, factors: vec![6, 8, 6, 15] };
+        assert_eq!(eq.solve(Operator::PART_ONE), None);
+        assert_eq!(eq.solve(Operator::PART_TWO), Some(vec![Operator::Mul, Operator::Concat, Operator::Mul]));
+        let eq: Equation = Equation { result: 0, factors: vec![7, 3, 0] };
+        assert_eq!(eq.solve(Operator::PART_ONE), Some(vec![Operator::Add, Operator::Mul]));
+        let eq: Equation = Equation { result: 0, factors: vec![u64::MAX, 1, 0] };
+        assert_eq!(eq.solve(Operator::PART_ONE), Some(vec![Operator::Mul, Operator::Mul]));
+    }
+
+    #[test]
+    fn overflow_is_never_a_solution() {
+        let eq: Equation = Equation { result: u64::MAX, factors: vec![u64::MAX, 2] };
+        assert_eq!(eq.solve(Operator::PART_TWO), None);
+        assert_eq!(eq.evaluate(&[Operator::Add]), None);
+        let eq: Equation = Equation { result: 18446744073709551615, factors: vec![1844674407370955161, 5] };
+        assert_eq!(eq.solve(Operator::PART_TWO), Some(vec![Operator::Concat]));
+        assert_eq!(Operator::Concat.apply(1844674407370955161, 6), None);
+        assert_eq!(Operator::Mul.apply(u64::MAX, 2), None);
+        let eq: Equation = Equation { result: u64::MAX, factors: vec![7, u64::MAX - 1] };
+        assert_eq!(eq.solve(Operator::PART_TWO), None);
+        assert_eq!(Operator::Concat.apply(7, u64::MAX - 1), None);
+        assert_eq!(Operator::Concat.apply(0, u64::MAX - 1), Some(u64::MAX - 1));
+    }
+
+    #[test]
+    fn totals_past_u64() {
+        let equations: Vec<Equation> = load_data("18446744073709551615: 18446744073709551615\n18446744073709551614: 9223372036854775807 2\n").unwrap();
+        assert_eq!(part_one(&equations), u64::MAX as u128 * 2 - 1);
+        assert_eq!(part_two(&equations), u64::MAX as u128 * 2 - 1);
+    }
+
+    #[test]
+    fn agrees_with_the_forward_search() {
+        let mut rng: Rng = Rng::new(0x2545f4914f6cdd1d);
+        for _ in 0..2000 {
+            // ? Mostly small factors, with zeros and values close to overflowing mixed in
+            let factors: Vec<u64> = (0..1 + rng.below(6))
+                .map(|_| match rng.below(10) {
+                    0 => 0,
+                    1 => u64::MAX - rng.below(100),
+                    2 => rng.next() >> rng.below(64),
+                    _ => 1 + rng.below(20),
+                })
+                .collect();
+            let ops: Vec<Operator> = (1..factors.len()).map(|_| Operator::PART_TWO[rng.below(3) as usize]).collect();
+            let mut eq: Equation = Equation { result: 0, factors };
+            // ? Half of them built to be solvable when they don't overflow, the rest off by a little
+            let noise: u64 = if rng.below(2) == 0 { 0 } else { rng.below(5) };
+            eq.result = eq.evaluate(&ops).unwrap_or_else(|| rng.next()).saturating_add(noise);
+            for (operators, concat) in [(Operator::PART_ONE, false), (Operator::PART_TWO, true)] {
+                let solved: Option<Vec<Operator>> = eq.solve(operators);
+                assert_eq!(solved.is_some(), forward(&eq, concat), "{:?}", eq);
+                if let Some(solved) = solved {
+                    assert_eq!(eq.evaluate(&solved), Some(eq.result));
+                    assert!(solved.iter().all(|op| operators.contains(op)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::ParseError;
+use crate::geometry::Pos;
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+#[derive(Clone, Copy)]
+pub enum Tile {
+    Empty,
+    Antenna(char),
+    Antinode,
+}
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Tile::Empty => write!(f, "."),
+            Tile::Antenna(c) => write!(f, "{}", c),
+            Tile::Antinode => write!(f, "#"),
+        }
+    }
+}
+impl std::fmt::Debug for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Tile::Empty => write!(f, "."),
+            Tile::Antenna(c) => write!(f, "{}", c),
+            Tile::Antinode => write!(f, "#"),
+        }
+    }
+}
+impl Tile {
+    fn is_empty(&self) -> bool {
+        matches!(self, Tile::Empty)
+    }
+    fn is_antenna(&self) -> bool {
+        matches!(self, Tile::Antenna(_))
+    }
+    fn get_antenna(&self) -> char {
+        match self {
+            Tile::Antenna(c) => *c,
+            _ => panic!("Tile is not an antenna"),
+        }
+    }
+}
+impl PartialEq for Tile {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Tile::Empty, Tile::Empty) => true,
+            (Tile::Antenna(c1), Tile::Antenna(c2)) => c1 == c2,
+            (Tile::Antinode, Tile::Antinode) => true,
+            _ => false,
+        }
+    }
+}
+impl Eq for Tile {}
+pub type Map = Grid<Tile>;
+
+pub fn load_data(input: &str) -> Result<Map, ParseError> {
+    Grid::parse(input, |c| match c {
+        '.' => Some(Tile::Empty),
+        _ => Some(Tile::Antenna(c)),
+    })
+}
+
+// ? Whether `p`, `p1` and `p2` lie on the same line, comparing the row over column slopes from `p`.
+fn in_line(p: Pos, p1: Pos, p2: Pos) -> bool {
+    (p1.y as f64 - p.y as f64) / (p1.x as f64 - p.x as f64) == (p2.y as f64 - p.y as f64) / (p2.x as f64 - p.x as f64)
+}
+
+fn antenna_positions(map: &Map) -> HashMap<char, Vec<Pos>> {
+    let mut antennas: HashMap<char, Vec<Pos>> = HashMap::new();
+    for (pos, tile) in map.iter() {
+        if tile.is_antenna() {
+            let antenna = tile.get_antenna();
+            let coords = antennas.entry(antenna).or_default();
+            coords.push(pos);
+        }
+    }
+    antennas
+}
+
+pub fn part_one(map: &Map) -> usize {
+    // ? Given a character of an antenna, i want to get a list of (x,y) coordinates where those antennas are located at.
+    let mut final_map: Map = map.clone();
+    let antennas: HashMap<char, Vec<Pos>> = antenna_positions(map);
+    let mut antinodes: HashSet<Pos> = HashSet::new();
+    for pos in map.positions() {
+        // ? For each tile we compute the distance_antennas map where each character of an antenna is mapped to a list of distances from that tile to the antennas.
+        let distances: HashMap<char, Vec<usize>> = antennas
+            .iter()
+            .map(|(antenna, coords)| {
+                let dists: Vec<usize> = coords
+                    .iter()
+                    .map(|coord| pos.manhattan(*coord))
+                    .collect();
+                (*antenna, dists)
+            })
+            .collect();
+
+        // ? now distances = {'0': [1, 2, 3], '1': [2, 3, 4], '2': [3, 4, 5], ...}
+        for (antenna, dists) in distances.iter() {
+            for (i, d1) in dists.iter().enumerate() {
+                for (j, d2) in dists.iter().skip(i + 1).enumerate() {
+                    if d1 * 2 == *d2 || d2 * 2 == *d1 {
+                        // ? if the two antennas are in line with one another and with the tile then it is in fact an antinode.
+                        if in_line(pos, antennas[antenna][i], antennas[antenna][j + i + 1]) {
+                            antinodes.insert(pos);
+                            if final_map[pos].is_empty() {
+                                final_map[pos] = Tile::Antinode;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    antinodes.len()
+}
+
+pub fn part_two(map: &Map) -> usize {
+    let mut final_map: Map = map.clone();
+    let antennas: HashMap<char, Vec<Pos>> = antenna_positions(map);
+    let mut antinodes: HashSet<Pos> = HashSet::new();
+    for pos in map.positions() {
+        let distances: HashMap<char, Vec<usize>> = antennas
+            .iter()
+            .map(|(antenna, coords)| {
+                let dists: Vec<usize> = coords
+                    .iter()
+                    .map(|coord| pos.manhattan(*coord))
+                    .collect();
+                (*antenna, dists)
+            })
+            .collect();
+
+        // ? now distances = {'0': [1, 2, 3], '1': [2, 3, 4], '2': [3, 4, 5], ...}
+        for (antenna, dists) in distances.iter() {
+            for (i, _) in dists.iter().enumerate() {
+                for (j, _) in dists.iter().skip(i + 1).enumerate() {
+                    // ? if the two antennas are in line with one another and with the tile then it is in fact an antinode.
+                    // ? Also consider the antenna itself as a possible antinode.
+                    if in_line(pos, antennas[antenna][i], antennas[antenna][j + i + 1]) {
+                        antinodes.insert(pos);
+                        if final_map[pos].is_empty() {
+                            final_map[pos] = Tile::Antinode;
+                        }
+                    }
+                }
+            }
+        }
+
+        // ? Also count all the occurrences of antennas that are not unique as antinodes.
+        for (antenna, dists) in distances.iter() {
+            if dists.len() > 1 {
+                antennas[antenna].iter().for_each(|coord| {
+                    antinodes.insert(*coord);
+                    if final_map[*coord].is_empty() {
+                        final_map[*coord] = Tile::Antinode;
+                    }
+                });
+            }
+        }
+    }
+
+    antinodes.len()
+}
+
+pub struct Day8;
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Map;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_data(input)
+    }
+    fn part_one(map: &Self::Input) -> usize {
+        part_one(map)
+    }
+    fn part_two(map: &Self::Input) -> usize {
+        part_two(map)
+    }
+}
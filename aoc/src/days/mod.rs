@@ -0,0 +1,72 @@
+use std::io::BufRead;
+use std::time::Duration;
+
+use crate::bench::{self, DayTimings};
+use crate::error::ParseError;
+use crate::solution::{Answer, Part, Solution};
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+pub type Solver = fn(&str, Part) -> Result<Answer, ParseError>;
+pub type Runner = fn(&mut dyn BufRead, &[Part]) -> Result<Vec<(Part, Answer, Duration)>, ParseError>;
+pub type Bencher = fn(&str, usize) -> Result<DayTimings, ParseError>;
+
+macro_rules! registry {
+    ($($day:literal => $solution:ty),* $(,)?) => {
+        /// Every day that has a registered solution, in calendar order.
+        pub const DAYS: &[u8] = &[$($day),*];
+
+        pub fn solver(day: u8) -> Option<Solver> {
+            match day {
+                $($day => Some(<$solution>::solve),)*
+                _ => None,
+            }
+        }
+
+        pub fn runner(day: u8) -> Option<Runner> {
+            match day {
+                $($day => Some(<$solution>::solve_parts),)*
+                _ => None,
+            }
+        }
+
+        pub fn bencher(day: u8) -> Option<Bencher> {
+            match day {
+                $($day => Some(bench::measure::<$solution>),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+registry! {
+    1 => day_1::Day1,
+    2 => day_2::Day2,
+    3 => day_3::Day3,
+    4 => day_4::Day4,
+    5 => day_5::Day5,
+    6 => day_6::Day6,
+    7 => day_7::Day7,
+    8 => day_8::Day8,
+    9 => day_9::Day9,
+    10 => day_10::Day10,
+    11 => day_11::Day11,
+    12 => day_12::Day12,
+    13 => day_13::Day13,
+    14 => day_14::Day14,
+    15 => day_15::Day15,
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A malformed puzzle input, located down to the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// `line` and `column` are 1-based, the way an editor shows them.
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            file: None,
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// An error about `token`, which must be a slice of `line`, the `idx`-th (0-based) line of the input.
+    pub fn at(idx: usize, line: &str, token: &str, message: impl Into<String>) -> ParseError {
+        let offset: usize = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        let column: usize = match line.get(..offset) {
+            Some(before) => before.chars().count() + 1,
+            None => 1,
+        };
+        ParseError::new(idx + 1, column, token, message)
+    }
+
+    /// The input stopped after `lines` lines while more was expected.
+    pub fn end_of_input(lines: usize, message: impl Into<String>) -> ParseError {
+        ParseError::new(lines + 1, 1, "", message)
+    }
+
+    pub fn with_file(mut self, file: impl AsRef<Path>) -> ParseError {
+        self.file = Some(file.as_ref().to_path_buf());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        if !self.text.is_empty() {
+            write!(f, " (found `{}`)", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `token`, a slice of the `idx`-th line `line`, reporting its position on failure.
+pub fn parse_token<T: FromStr>(idx: usize, line: &str, token: &str) -> Result<T, ParseError> {
+    token.parse::<T>().map_err(|_| {
+        ParseError::at(idx, line, token, format!("expected a value of type {}", std::any::type_name::<T>()))
+    })
+}
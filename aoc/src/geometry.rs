@@ -0,0 +1,255 @@
+use std::fmt;
+use std::ops::Add;
+
+/// A cell of a grid: `x` is the column, `y` is the row, both counted from the top-left corner.
+/// Positions order row by row, the way the grid reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Pos {
+    pub y: usize,
+    pub x: usize,
+}
+
+impl Pos {
+    pub const fn new(x: usize, y: usize) -> Pos {
+        Pos { x, y }
+    }
+
+    /// Move by `(dx, dy)`, or `None` if that would leave the non-negative quadrant.
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Pos> {
+        Some(Pos {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    /// One step towards `dir`, or `None` if that would leave the non-negative quadrant.
+    pub fn checked_add(self, dir: impl Direction) -> Option<Pos> {
+        let (dx, dy) = dir.delta();
+        self.offset(dx, dy)
+    }
+
+    /// One step towards `dir` on a `width` by `height` torus, coming back in from the opposite edge.
+    pub fn wrapping_add(self, dir: impl Direction, width: usize, height: usize) -> Pos {
+        let (dx, dy) = dir.delta();
+        Pos {
+            x: (self.x as isize + dx).rem_euclid(width as isize) as usize,
+            y: (self.y as isize + dy).rem_euclid(height as isize) as usize,
+        }
+    }
+
+    pub fn manhattan(self, other: Pos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Pos) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl<D: Direction> Add<D> for Pos {
+    type Output = Pos;
+
+    /// Panics when stepping off the top or left edge, use `checked_add` when that can happen.
+    fn add(self, dir: D) -> Pos {
+        self.checked_add(dir)
+            .unwrap_or_else(|| panic!("stepping from {} leaves the grid", self))
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Anything that moves a `Pos` by one cell.
+pub trait Direction: Copy + 'static {
+    /// The `(dx, dy)` of one step, with `y` growing downwards.
+    fn delta(self) -> (isize, isize);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Direction4; 4] = [Direction4::Up, Direction4::Right, Direction4::Down, Direction4::Left];
+
+    pub fn turn_right(self) -> Direction4 {
+        match self {
+            Direction4::Up => Direction4::Right,
+            Direction4::Right => Direction4::Down,
+            Direction4::Down => Direction4::Left,
+            Direction4::Left => Direction4::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction4 {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(self) -> Direction4 {
+        self.turn_right().turn_right()
+    }
+
+    /// Parse one of `^`, `>`, `v`, `<`.
+    pub fn from_arrow(c: char) -> Option<Direction4> {
+        match c {
+            '^' => Some(Direction4::Up),
+            '>' => Some(Direction4::Right),
+            'v' => Some(Direction4::Down),
+            '<' => Some(Direction4::Left),
+            _ => None,
+        }
+    }
+
+    /// Parse one of `U`, `R`, `D`, `L`.
+    pub fn from_letter(c: char) -> Option<Direction4> {
+        match c {
+            'U' => Some(Direction4::Up),
+            'R' => Some(Direction4::Right),
+            'D' => Some(Direction4::Down),
+            'L' => Some(Direction4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_arrow(self) -> char {
+        match self {
+            Direction4::Up => '^',
+            Direction4::Right => '>',
+            Direction4::Down => 'v',
+            Direction4::Left => '<',
+        }
+    }
+}
+
+impl Direction for Direction4 {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction4::Up => (0, -1),
+            Direction4::Right => (1, 0),
+            Direction4::Down => (0, 1),
+            Direction4::Left => (-1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// Rotate clockwise by 45 degrees.
+    pub fn turn_right(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Rotate counter-clockwise by 45 degrees.
+    pub fn turn_left(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl Direction for Direction8 {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction8::Up => (0, -1),
+            Direction8::UpRight => (1, -1),
+            Direction8::Right => (1, 0),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (0, 1),
+            Direction8::DownLeft => (-1, 1),
+            Direction8::Left => (-1, 0),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(dir: Direction4) -> Direction8 {
+        match dir {
+            Direction4::Up => Direction8::Up,
+            Direction4::Right => Direction8::Right,
+            Direction4::Down => Direction8::Down,
+            Direction4::Left => Direction8::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_around_every_corner() {
+        assert_eq!(Pos::new(0, 0).wrapping_add(Direction8::UpLeft, 11, 7), Pos::new(10, 6));
+        assert_eq!(Pos::new(10, 0).wrapping_add(Direction8::UpRight, 11, 7), Pos::new(0, 6));
+        assert_eq!(Pos::new(10, 6).wrapping_add(Direction8::DownRight, 11, 7), Pos::new(0, 0));
+        assert_eq!(Pos::new(0, 6).wrapping_add(Direction8::DownLeft, 11, 7), Pos::new(10, 0));
+        assert_eq!(Pos::new(5, 3).wrapping_add(Direction4::Up, 11, 7), Pos::new(5, 2));
+    }
+
+    #[test]
+    fn checked_steps_stop_at_the_origin() {
+        assert_eq!(Pos::new(0, 3).checked_add(Direction4::Left), None);
+        assert_eq!(Pos::new(3, 0).checked_add(Direction8::UpRight), None);
+        assert_eq!(Pos::new(3, 0).checked_add(Direction8::DownLeft), Some(Pos::new(2, 1)));
+        assert_eq!(Pos::new(1, 1).offset(-1, -1), Some(Pos::default()));
+        assert_eq!(Pos::new(1, 1) + Direction4::Down, Pos::new(1, 2));
+    }
+
+    #[test]
+    fn turns_compose_to_identity() {
+        for dir in Direction4::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right().turn_right().turn_right(), dir);
+            assert_eq!(dir.opposite().opposite(), dir);
+            assert_eq!(Direction4::from_arrow(dir.to_arrow()), Some(dir));
+            assert_eq!(Direction8::from(dir.turn_right()), Direction8::from(dir).turn_right().turn_right());
+            let (dx, dy) = dir.delta();
+            assert_eq!(dir.opposite().delta(), (-dx, -dy));
+        }
+        for dir in Direction8::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!((0..8).fold(dir, |dir, _| dir.turn_right()), dir);
+            assert_eq!(dir.opposite(), dir.turn_left().turn_left().turn_left().turn_left());
+        }
+    }
+
+    #[test]
+    fn orders_row_by_row() {
+        assert!(Pos::new(5, 0) < Pos::new(0, 1));
+        assert_eq!(Pos::new(1, 4).manhattan(Pos::new(4, 0)), 7);
+        assert_eq!(Pos::new(1, 4).chebyshev(Pos::new(4, 0)), 4);
+    }
+}
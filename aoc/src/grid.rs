@@ -0,0 +1,234 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+use crate::geometry::{Direction, Direction4, Direction8, Pos};
+
+/// A rectangular map stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// Build a grid from its cells in row-major order, `None` if they don't fill `width * height`.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        if cells.len() != width * height {
+            return None;
+        }
+        Some(Grid { width, height, cells })
+    }
+
+    /// Parse one cell per character and one row per line; `f` rejects a character by returning `None`.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let mut cells: Vec<T> = Vec::new();
+        let mut width: usize = 0;
+        let mut height: usize = 0;
+        for (idx, line) in input.trim_end().lines().enumerate() {
+            for (col, c) in line.char_indices() {
+                let cell: T = f(c).ok_or_else(|| {
+                    ParseError::at(idx, line, &line[col..col + c.len_utf8()], "invalid character")
+                })?;
+                cells.push(cell);
+            }
+            let row_width: usize = cells.len() - width * height;
+            if height == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(ParseError::at(idx, line, line, format!("expected a row of {} cells", width)));
+            }
+            height += 1;
+        }
+        Ok(Grid { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        self.cells.get(pos.y * self.width + pos.x)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        self.cells.get_mut(pos.y * self.width + pos.x)
+    }
+
+    /// Every position of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width: usize = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// One step from `pos` towards `dir`, or `None` when that leaves the grid.
+    pub fn step(&self, pos: Pos, dir: impl Direction) -> Option<Pos> {
+        pos.checked_add(dir).filter(|next| self.contains(*next))
+    }
+
+    /// The in-bounds orthogonal neighbors of `pos`, clockwise from the one above.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction4::ALL.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbors of `pos`, clockwise from the one above.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction8::ALL.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Positions from `start` stepping towards `dir` until the edge of the grid, `start` included.
+    pub fn walk<D: Direction>(&self, start: Pos, dir: D) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(Some(start).filter(|pos| self.contains(*pos)), move |pos| self.step(*pos, dir))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Every line running down-right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height).rev().map(|y| Pos::new(0, y));
+        let starts = starts.chain((1..self.width).map(|x| Pos::new(x, 0)));
+        starts.map(move |start| self.walk(start, Direction8::DownRight).map(move |pos| &self[pos]))
+    }
+
+    /// Every line running down-left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width).map(|x| Pos::new(x, 0));
+        let starts = starts.chain((1..self.height).map(|y| Pos::new(self.width.saturating_sub(1), y)));
+        starts.map(move |start| self.walk(start, Direction8::DownLeft).map(move |pos| &self[pos]))
+    }
+
+    /// The first position, row by row, whose cell satisfies `pred`.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter().filter(move |(_, cell)| *cell == value).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{} is outside of a {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{} is outside of a {}x{} grid", pos, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(input: &str) -> Grid<char> {
+        Grid::parse(input, Some).unwrap()
+    }
+
+    fn lines<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn diagonals_on_wide_and_tall_grids() {
+        let wide: Grid<char> = letters("abc\ndef\n");
+        assert_eq!((wide.width(), wide.height()), (3, 2));
+        assert_eq!(lines(wide.diagonals()), ["d", "ae", "bf", "c"]);
+        assert_eq!(lines(wide.anti_diagonals()), ["a", "bd", "ce", "f"]);
+
+        let tall: Grid<char> = letters("ab\ncd\nef\n");
+        assert_eq!(lines(tall.diagonals()), ["e", "cf", "ad", "b"]);
+        assert_eq!(lines(tall.anti_diagonals()), ["a", "bc", "de", "f"]);
+        assert_eq!(lines(tall.columns()), ["ace", "bdf"]);
+    }
+
+    #[test]
+    fn steps_stay_inside() {
+        let grid: Grid<char> = letters("abc\ndef\n");
+        assert_eq!(grid.step(Pos::new(0, 0), Direction4::Left), None);
+        assert_eq!(grid.step(Pos::new(2, 1), Direction8::DownRight), None);
+        assert_eq!(grid.neighbors4(Pos::new(0, 0)).collect::<Vec<Pos>>(), [Pos::new(1, 0), Pos::new(0, 1)]);
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 5);
+        assert_eq!(grid.walk(Pos::new(0, 1), Direction8::UpRight).collect::<Vec<Pos>>(), [Pos::new(0, 1), Pos::new(1, 0)]);
+        assert_eq!(grid.walk(Pos::new(3, 0), Direction4::Left).count(), 0);
+    }
+
+    #[test]
+    fn rejects_ragged_rows_and_unknown_cells() {
+        let short: ParseError = Grid::parse("abc\nde\nfgh\n", Some).unwrap_err();
+        assert_eq!((short.line, short.column, short.message.as_str()), (2, 1, "expected a row of 3 cells"));
+        let long: ParseError = Grid::parse("ab\ncde\n", Some).unwrap_err();
+        assert_eq!((long.line, long.text.as_str()), (2, "cde"));
+        let unknown: ParseError = Grid::parse("..\n.#\n", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!((unknown.line, unknown.column, unknown.text.as_str()), (2, 2, "#"));
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The event every day of this crate belongs to.
+pub const YEAR: u16 = 2024;
+
+/// Environment variable holding the session cookie of the puzzle site.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding where inputs are cached.
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+/// Environment variable pointing every request at another server, e.g. a local mock of the site.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/giulianbiolo/advent_of_code_2024";
+
+/// `AOC_BASE_URL` if set, otherwise the puzzle site.
+pub(crate) fn base_url() -> String {
+    std::env::var(BASE_URL_VAR).unwrap_or_else(|_| BASE_URL.to_string()).trim_end_matches('/').to_string()
+}
+
+/// The HTTP client shared by every request to the puzzle site.
+pub(crate) fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .build()
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(PathBuf, io::Error),
+    NotCached(PathBuf),
+    Http(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Io(path, err) => write!(f, "cannot access {}: {}", path.display(), err),
+            InputError::NotCached(path) => write!(
+                f,
+                "{} does not exist, place the input there or set {} to download it",
+                path.display(),
+                SESSION_VAR
+            ),
+            InputError::Http(message) => write!(f, "download failed: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Somewhere puzzle inputs come from.
+pub trait InputProvider {
+    fn input(&self, day: u8) -> Result<String, InputError>;
+    /// The file the input of `day` is read from, used to locate parse errors.
+    fn path(&self, day: u8) -> PathBuf;
+}
+
+/// Inputs stored on disk as `<root>/<year>/<day>.txt`.
+pub struct FileCache {
+    root: PathBuf,
+    year: u16,
+}
+
+impl FileCache {
+    pub fn new(root: impl Into<PathBuf>, year: u16) -> FileCache {
+        FileCache { root: root.into(), year }
+    }
+
+    pub fn store(&self, day: u8, data: &str) -> Result<(), InputError> {
+        let path: PathBuf = self.path(day);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|err| InputError::Io(dir.to_path_buf(), err))?;
+        }
+        std::fs::write(&path, data).map_err(|err| InputError::Io(path, err))
+    }
+}
+
+impl InputProvider for FileCache {
+    fn input(&self, day: u8) -> Result<String, InputError> {
+        let path: PathBuf = self.path(day);
+        match std::fs::read_to_string(&path) {
+            Ok(data) => Ok(data),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Err(InputError::NotCached(path)),
+            Err(err) => Err(InputError::Io(path, err)),
+        }
+    }
+
+    fn path(&self, day: u8) -> PathBuf {
+        self.root.join(self.year.to_string()).join(format!("{}.txt", day))
+    }
+}
+
+/// Downloads inputs missing from `cache` with the account's session cookie, then keeps them there
+/// so each day is fetched at most once.
+pub struct HttpProvider {
+    cache: FileCache,
+    session: String,
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl HttpProvider {
+    pub fn new(cache: FileCache, session: impl Into<String>) -> HttpProvider {
+        HttpProvider {
+            cache,
+            session: session.into(),
+            base_url: base_url(),
+            agent: agent(),
+        }
+    }
+
+    /// Talk to another server than the puzzle site, e.g. a local stand-in during tests.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> HttpProvider {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn fetch(&self, day: u8) -> Result<String, InputError> {
+        let url: String = format!("{}/{}/day/{}/input", self.base_url, self.cache.year, day);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(code, _) => InputError::Http(format!("{} answered {}", url, code)),
+                ureq::Error::Transport(err) => InputError::Http(err.to_string()),
+            })?;
+        response.into_string().map_err(|err| InputError::Http(err.to_string()))
+    }
+}
+
+impl InputProvider for HttpProvider {
+    fn input(&self, day: u8) -> Result<String, InputError> {
+        match self.cache.input(day) {
+            Err(InputError::NotCached(_)) => {
+                let data: String = self.fetch(day)?;
+                self.cache.store(day, &data)?;
+                Ok(data)
+            }
+            cached => cached,
+        }
+    }
+
+    fn path(&self, day: u8) -> PathBuf {
+        self.cache.path(day)
+    }
+}
+
+/// The session cookie from `AOC_SESSION`, or else from `~/.config/aoc/session`.
+pub fn session_token() -> Option<String> {
+    if let Ok(token) = std::env::var(SESSION_VAR) {
+        return Some(token.trim().to_string()).filter(|token| !token.is_empty());
+    }
+    let config_dir: PathBuf = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    let token: String = std::fs::read_to_string(config_dir.join("aoc").join("session")).ok()?;
+    Some(token.trim().to_string()).filter(|token| !token.is_empty())
+}
+
+/// `AOC_INPUTS` if set, otherwise the `inputs` directory at the root of the workspace.
+pub fn cache_dir() -> PathBuf {
+    match std::env::var_os(INPUTS_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().join("inputs"),
+    }
+}
+
+/// The local cache, downloading into it when a session token is configured.
+pub fn default_provider() -> Box<dyn InputProvider> {
+    let cache: FileCache = FileCache::new(cache_dir(), YEAR);
+    match session_token() {
+        Some(session) => Box::new(HttpProvider::new(cache, session)),
+        None => Box::new(cache),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::stand_in_server;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir: PathBuf = std::env::temp_dir().join(format!("aoc-input-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn file_cache_reads_year_and_day() {
+        let dir: PathBuf = scratch_dir("cache");
+        let cache: FileCache = FileCache::new(&dir, 2024);
+        assert!(matches!(cache.input(3), Err(InputError::NotCached(_))));
+        cache.store(3, "mul(2,4)").unwrap();
+        assert_eq!(cache.path(3), dir.join("2024").join("3.txt"));
+        assert_eq!(cache.input(3).unwrap(), "mul(2,4)");
+    }
+
+    #[test]
+    fn http_provider_downloads_once_then_uses_the_cache() {
+        let (url, requests) = stand_in_server("200 OK", "3   4\n4   3\n");
+        let dir: PathBuf = scratch_dir("http");
+        let provider: HttpProvider = HttpProvider::new(FileCache::new(&dir, 2024), "secret").with_base_url(url);
+
+        assert_eq!(provider.input(1).unwrap(), "3   4\n4   3\n");
+        assert_eq!(provider.input(1).unwrap(), "3   4\n4   3\n");
+        assert_eq!(std::fs::read_to_string(dir.join("2024").join("1.txt")).unwrap(), "3   4\n4   3\n");
+
+        let requests: Vec<String> = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2024/day/1/input "));
+        assert!(requests[0].contains("session=secret"));
+    }
+
+    #[test]
+    fn http_provider_does_not_cache_failures() {
+        let (url, _) = stand_in_server("400 Bad Request", "Puzzle inputs differ by user.");
+        let dir: PathBuf = scratch_dir("http-error");
+        let provider: HttpProvider = HttpProvider::new(FileCache::new(&dir, 2024), "expired").with_base_url(url);
+
+        assert!(matches!(provider.input(2), Err(InputError::Http(_))));
+        assert!(!dir.join("2024").join("2.txt").exists());
+    }
+}
@@ -0,0 +1,17 @@
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod report;
+pub mod solution;
+pub mod submit;
+pub mod testing;
+
+pub use error::ParseError;
+pub use geometry::{Direction, Direction4, Direction8, Pos};
+pub use grid::Grid;
+pub use input::{InputError, InputProvider};
+pub use solution::{run, Answer, Part, Solution, Unsolved};
@@ -0,0 +1,570 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
+
+use aoc::answers::{AnswerDb, AnswerDbError, Verdict};
+use aoc::bench::{DayTimings, Nanos};
+use aoc::days::day_1::{self, Columns};
+use aoc::days::day_2::{self, SafetyRule};
+use aoc::days::day_5::{self, Analysis};
+use aoc::days::day_6::{self, LoopFinder, PatrolTrace};
+use aoc::days::{self, Bencher, Solver};
+use aoc::input::{self, InputProvider};
+use aoc::report::{self, Format, Record};
+use aoc::submit::{Outcome, Submitter, WrongAnswers};
+use aoc::{Answer, ParseError, Part, Pos};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2024 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day, or only one of its parts
+    Run {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input to read, defaults to the cached `inputs/<year>/<day>.txt`
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+        #[arg(long, short, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Compare fresh answers against the accepted ones in `answers.toml`
+    Verify {
+        /// Only verify this day, defaults to every day
+        day: Option<u8>,
+        /// Store the answers of parts that have none recorded yet
+        #[arg(long)]
+        record: bool,
+    },
+    /// Time parsing and each part separately
+    Bench {
+        /// Only benchmark this day, defaults to every day
+        day: Option<u8>,
+        /// Timed runs per phase, after one warm-up run
+        #[arg(long, short = 'n', default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+        /// Print the timings as JSON, e.g. to diff runs across commits
+        #[arg(long)]
+        json: bool,
+    },
+    /// Post an answer to the puzzle site
+    Submit {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Answer to post instead of solving the cached input
+        #[arg(long)]
+        answer: Option<String>,
+    },
+    /// Explain the answer of a day with a detailed report
+    Report {
+        #[command(subcommand)]
+        report: Report,
+    },
+}
+
+#[derive(Subcommand)]
+enum Report {
+    /// Which values make up the similarity score of the two lists
+    Day1 {
+        /// How many of the largest contributors to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Also list what every value contributes
+        #[arg(long)]
+        breakdown: bool,
+        /// Print the histogram of both lists as CSV instead
+        #[arg(long)]
+        histogram: bool,
+        /// Puzzle input to read, defaults to the cached `inputs/<year>/<day>.txt`
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+    },
+    /// Why each report is safe or not
+    Day2 {
+        /// Judge with the rule of this part, which allows one removal in part two
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Print every report with its violation highlighted, not only the count
+        #[arg(long)]
+        explain: bool,
+        /// Only print the unsafe reports
+        #[arg(long, requires = "explain")]
+        unsafe_only: bool,
+        /// Puzzle input to read, defaults to the cached `inputs/<year>/<day>.txt`
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+    },
+    /// Whether the ordering rules are consistent: duplicates, contradictions, cycles, unknown pages
+    Day5 {
+        /// Puzzle input to read, defaults to the cached `inputs/<year>/<day>.txt`
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+    },
+    /// Replay the guard's patrol as ASCII frames or an animated GIF
+    Day6 {
+        /// Mark every position where an obstruction would trap the guard with `O`
+        #[arg(long)]
+        obstructions: bool,
+        /// Write one text file per frame into this directory
+        #[arg(long)]
+        frames: Option<PathBuf>,
+        /// Write the frames as an animated GIF
+        #[arg(long)]
+        gif: Option<PathBuf>,
+        /// Moves of the guard between two frames
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        every: u32,
+        /// Pixels per cell in the GIF
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=32))]
+        scale: u32,
+        /// Puzzle input to read, defaults to the cached `inputs/<year>/<day>.txt`
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+    },
+}
+
+// ? An explicit file wins, otherwise ask the input cache (which may download the day).
+fn read_input(day: u8, input: Option<PathBuf>) -> Result<(PathBuf, String), String> {
+    match input {
+        Some(path) => match std::fs::read_to_string(&path) {
+            Ok(data) => Ok((path, data)),
+            Err(err) => Err(format!("cannot read {}: {}", path.display(), err)),
+        },
+        None => {
+            let provider: Box<dyn InputProvider> = input::default_provider();
+            let data: String = provider.input(day).map_err(|err| err.to_string())?;
+            Ok((provider.path(day), data))
+        }
+    }
+}
+
+// ? Like `read_input`, but only makes sure the file exists so callers can stream it.
+fn input_path(day: u8, input: Option<PathBuf>) -> Result<PathBuf, String> {
+    match input {
+        Some(path) => Ok(path),
+        None => {
+            let provider: Box<dyn InputProvider> = input::default_provider();
+            let path: PathBuf = provider.path(day);
+            if !path.exists() {
+                provider.input(day).map_err(|err| err.to_string())?;
+            }
+            Ok(path)
+        }
+    }
+}
+
+fn open_input(path: &Path) -> Result<BufReader<File>, String> {
+    File::open(path).map(BufReader::new).map_err(|err| format!("cannot read {}: {}", path.display(), err))
+}
+
+fn run(day: u8, part: Option<u8>, input: Option<PathBuf>, format: Format) -> ExitCode {
+    let Some(runner) = days::runner(day) else {
+        eprintln!("error: day {} has no registered solution", day);
+        return ExitCode::FAILURE;
+    };
+    let parts: Vec<Part> = match part {
+        Some(part) => vec![Part::from_number(part).expect("part is validated by clap")],
+        None => Part::ALL.to_vec(),
+    };
+    // ? Only the real input is expected to give the recorded answer
+    let custom_input: bool = input.is_some();
+    let path: PathBuf = match input_path(day, input) {
+        Ok(path) => path,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let db: Option<AnswerDb> = if custom_input { None } else { AnswerDb::load(AnswerDb::default_path()).ok() };
+
+    let mut reader: BufReader<File> = match open_input(&path) {
+        Ok(reader) => reader,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    // ? Parsed once for every part, and only the parts are timed, like `bench` does
+    let solved: Vec<(Part, Answer, Duration)> = match runner(&mut reader, &parts) {
+        Ok(solved) => solved,
+        Err(err) => {
+            eprintln!("error: {}", err.with_file(&path));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut records: Vec<Record> = Vec::new();
+    for (part, answer, elapsed) in solved {
+        let elapsed_ns: u64 = elapsed.as_nanos() as u64;
+        if let Answer::Error(message) = &answer {
+            eprintln!("error: {}: {}", part, message);
+            return ExitCode::FAILURE;
+        }
+        if let Some(Verdict::Fail { expected }) = db.as_ref().map(|db| db.check(day, part, &answer)) {
+            eprintln!("warning: answers.toml expects {} for {}", expected, part);
+        }
+        records.push(Record::new(day, part, &answer, elapsed_ns));
+    }
+    print!("{}", report::render(&records, format));
+    ExitCode::SUCCESS
+}
+
+fn verify(day: Option<u8>, record: bool) -> ExitCode {
+    let days: Vec<u8> = match day {
+        Some(day) if days::solver(day).is_none() => {
+            eprintln!("error: day {} has no registered solution", day);
+            return ExitCode::FAILURE;
+        }
+        Some(day) => vec![day],
+        None => days::DAYS.to_vec(),
+    };
+    let db_path: PathBuf = AnswerDb::default_path();
+    let mut db: AnswerDb = match AnswerDb::load(&db_path) {
+        Ok(db) => db,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed: bool = false;
+    let mut recorded: usize = 0;
+    println!("{:>3}  {:>4}  {:<20}  {:<20}  status", "day", "part", "answer", "expected");
+    for day in days {
+        let solver: Solver = days::solver(day).unwrap();
+        let (path, data) = match read_input(day, None) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                failed = true;
+                continue;
+            }
+        };
+        for part in Part::ALL {
+            let answer: Answer = match solver(&data, part) {
+                Ok(answer) => answer,
+                Err(err) => {
+                    eprintln!("error: {}", err.with_file(&path));
+                    failed = true;
+                    break;
+                }
+            };
+            let mut verdict: Verdict = db.check(day, part, &answer);
+            // ? Recording only fills in unknown answers, a mismatch has to be fixed (or edited) by hand
+            if record && verdict == Verdict::Unknown && answer != Answer::Unsolved {
+                db.record(day, part, &answer);
+                recorded += 1;
+                verdict = Verdict::Pass;
+            }
+            let expected: String = match &verdict {
+                Verdict::Fail { expected } => expected.clone(),
+                Verdict::Pass => answer.to_string(),
+                Verdict::Unknown => "-".to_string(),
+            };
+            failed |= matches!(verdict, Verdict::Fail { .. });
+            println!("{:>3}  {:>4}  {:<20}  {:<20}  {}", day, part.number(), answer.to_string(), expected, verdict);
+        }
+    }
+
+    if recorded > 0 {
+        if let Err(err) = db.save(&db_path) {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+        println!("recorded {} new answer(s) in {}", recorded, db_path.display());
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench(day: Option<u8>, iterations: usize, json: bool) -> ExitCode {
+    let days: Vec<u8> = match day {
+        Some(day) if days::bencher(day).is_none() => {
+            eprintln!("error: day {} has no registered solution", day);
+            return ExitCode::FAILURE;
+        }
+        Some(day) => vec![day],
+        None => days::DAYS.to_vec(),
+    };
+
+    let mut failed: bool = false;
+    let mut results: Vec<DayTimings> = Vec::new();
+    if !json {
+        println!("{:>3}  {:<8}  {:>10}  {:>10}  {:>10}  {:>10}", "day", "phase", "min", "median", "mean", "stddev");
+    }
+    for day in days {
+        let bencher: Bencher = days::bencher(day).unwrap();
+        let timings: DayTimings = match read_input(day, None) {
+            Ok((path, data)) => match bencher(&data, iterations) {
+                Ok(timings) => timings,
+                Err(err) => {
+                    eprintln!("error: {}", err.with_file(&path));
+                    failed = true;
+                    continue;
+                }
+            },
+            Err(err) => {
+                eprintln!("error: {}", err);
+                failed = true;
+                continue;
+            }
+        };
+        if !json {
+            for (phase, stats) in [("parse", timings.parse), ("part one", timings.part_one), ("part two", timings.part_two)] {
+                println!(
+                    "{:>3}  {:<8}  {:>10}  {:>10}  {:>10}  {:>10}",
+                    day,
+                    phase,
+                    Nanos(stats.min_ns),
+                    Nanos(stats.median_ns),
+                    Nanos(stats.mean_ns),
+                    Nanos(stats.stddev_ns)
+                );
+            }
+        }
+        results.push(timings);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results).expect("timings serialize to JSON"));
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn submit(day: u8, part: u8, answer: Option<String>) -> ExitCode {
+    let part: Part = Part::from_number(part).expect("part is validated by clap");
+    let answer: Answer = match answer {
+        Some(answer) => Answer::parse(answer.trim()),
+        None => {
+            let Some(solver) = days::solver(day) else {
+                eprintln!("error: day {} has no registered solution", day);
+                return ExitCode::FAILURE;
+            };
+            let (path, data) = match read_input(day, None) {
+                Ok(input) => input,
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    return ExitCode::FAILURE;
+                }
+            };
+            match solver(&data, part) {
+                Ok(answer) => answer,
+                Err(err) => {
+                    eprintln!("error: {}", err.with_file(&path));
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    };
+    match &answer {
+        Answer::Unsolved => {
+            eprintln!("error: day {} {} is not solved yet", day, part);
+            return ExitCode::FAILURE;
+        }
+        Answer::Error(message) => {
+            eprintln!("error: {}: {}", part, message);
+            return ExitCode::FAILURE;
+        }
+        _ => (),
+    }
+
+    let db_path: PathBuf = AnswerDb::default_path();
+    let wrong_path: PathBuf = WrongAnswers::default_path();
+    let (mut db, mut wrong) = match (AnswerDb::load(&db_path), WrongAnswers::load(&wrong_path)) {
+        (Ok(db), Ok(wrong)) => (db, wrong),
+        (Err(err), _) | (_, Err(err)) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    // ? Never spend a submission (and a lockout) on something already known
+    if let Some(expected) = db.expected(day, part) {
+        eprintln!("error: answers.toml already accepts {} for day {} {}", expected, day, part);
+        return ExitCode::FAILURE;
+    }
+    if let Some(reason) = wrong.refusal(day, part, &answer) {
+        eprintln!("error: not submitting {}, {}", answer, reason);
+        return ExitCode::FAILURE;
+    }
+    if let Some(wait) = wrong.lockout(SystemTime::now()) {
+        eprintln!("error: the site refuses answers for another {}s", wait.as_secs());
+        return ExitCode::FAILURE;
+    }
+    let Some(session) = input::session_token() else {
+        eprintln!("error: set {} to submit answers", input::SESSION_VAR);
+        return ExitCode::FAILURE;
+    };
+
+    let outcome: Outcome = match Submitter::new(session).submit(day, part, &answer) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("day {} {}: {} is {}", day, part, answer, outcome);
+    let saved: Result<(), AnswerDbError> = match outcome {
+        Outcome::Correct => {
+            db.record(day, part, &answer);
+            db.save(&db_path)
+        }
+        Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect | Outcome::Wait(_) => {
+            wrong.record(day, part, &answer, outcome);
+            wrong.lock_out(outcome, SystemTime::now());
+            wrong.save(&wrong_path)
+        }
+        Outcome::AlreadySolved => Ok(()),
+    };
+    if let Err(err) = saved {
+        eprintln!("error: {}", err);
+        return ExitCode::FAILURE;
+    }
+    match outcome {
+        Outcome::Correct | Outcome::AlreadySolved => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+// ? Read and parse a day's input for a report, printing what went wrong otherwise.
+fn load<T>(day: u8, input: Option<PathBuf>, parse: impl Fn(&str) -> Result<T, ParseError>) -> Option<T> {
+    let (path, data) = match read_input(day, input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return None;
+        }
+    };
+    match parse(&data) {
+        Ok(parsed) => Some(parsed),
+        Err(err) => {
+            eprintln!("error: {}", err.with_file(&path));
+            None
+        }
+    }
+}
+
+// ? Day 1 inputs can be large, so the report streams them instead of going through `load`.
+fn load_day1(input: Option<PathBuf>) -> Option<Columns<i64>> {
+    let path: PathBuf = input_path(1, input).map_err(|err| eprintln!("error: {}", err)).ok()?;
+    let reader: BufReader<File> = open_input(&path).map_err(|err| eprintln!("error: {}", err)).ok()?;
+    match day_1::read_columns(reader, Some(2)) {
+        Ok(columns) => Some(columns),
+        Err(err) => {
+            eprintln!("error: {}", err.with_file(&path));
+            None
+        }
+    }
+}
+
+fn report(report: Report) -> ExitCode {
+    match report {
+        Report::Day1 { top, breakdown, histogram, input } => {
+            let Some(columns) = load_day1(input) else {
+                return ExitCode::FAILURE;
+            };
+            if histogram {
+                print!("{}", day_1::histogram_csv(&columns));
+            } else {
+                match day_1::report(&columns, top, breakdown) {
+                    Ok(report) => print!("{}", report),
+                    Err(err) => {
+                        eprintln!("error: {}", err);
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+        }
+        Report::Day2 { part, explain, unsafe_only, input } => {
+            let Some(reports) = load(2, input, day_2::load_input) else {
+                return ExitCode::FAILURE;
+            };
+            let rule: SafetyRule = if part == 1 { SafetyRule::PART_ONE } else { SafetyRule::PART_TWO };
+            if explain {
+                print!("{}", day_2::explain(&reports, &rule, unsafe_only));
+            } else {
+                let safe: usize = reports.iter().filter(|report| rule.is_safe(report)).count();
+                println!("{} of {} reports are safe", safe, reports.len());
+            }
+        }
+        Report::Day5 { input } => {
+            let Some((rules, updates)) = load(5, input, day_5::load_data) else {
+                return ExitCode::FAILURE;
+            };
+            let analysis: Analysis = day_5::analyze(&rules, &updates);
+            print!("{}", analysis);
+            if !analysis.is_sound() {
+                return ExitCode::FAILURE;
+            }
+        }
+        Report::Day6 { obstructions, frames, gif, every, scale, input } => {
+            let Some(map) = load(6, input, day_6::load_data) else {
+                return ExitCode::FAILURE;
+            };
+            let trace: PatrolTrace = PatrolTrace::record(&map);
+            let overlay: Vec<Pos> = if obstructions { LoopFinder::new(&map).with_jumps().obstructions() } else { Vec::new() };
+            if let Some(dir) = frames {
+                let written: std::io::Result<usize> = std::fs::create_dir_all(&dir).and_then(|_| {
+                    let mut count: usize = 0;
+                    for frame in trace.frames(every as usize, &overlay) {
+                        std::fs::write(dir.join(format!("frame_{:05}.txt", count)), frame)?;
+                        count += 1;
+                    }
+                    Ok(count)
+                });
+                match written {
+                    Ok(count) => println!("wrote {} frames to {}", count, dir.display()),
+                    Err(err) => {
+                        eprintln!("error: cannot write frames to {}: {}", dir.display(), err);
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+            if let Some(path) = gif {
+                let written: std::io::Result<()> = std::fs::File::create(&path)
+                    .and_then(|file| trace.write_gif(std::io::BufWriter::new(file), every as usize, scale as usize, &overlay));
+                match written {
+                    Ok(()) => println!("wrote {}", path.display()),
+                    Err(err) => {
+                        eprintln!("error: cannot write {}: {}", path.display(), err);
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+            print!("{}", trace.render(trace.events().len(), &overlay));
+            let ending: &str = if trace.looped() { "loops forever" } else { "leaves the map" };
+            println!("the guard visits {} cells in {} moves and {}", trace.visited(), trace.events().len(), ending);
+            if obstructions {
+                println!("{} positions would trap it in a loop", overlay.len());
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let cli: Cli = Cli::parse();
+    match cli.command {
+        Command::Run { day, part, input, format } => run(day, part, input, format),
+        Command::Verify { day, record } => verify(day, record),
+        Command::Bench { day, iterations, json } => bench(day, iterations as usize, json),
+        Command::Submit { day, part, answer } => submit(day, part, answer),
+        Command::Report { report: which } => report(which),
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::solution::{Answer, Part};
+
+/// How the runner prints its answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    /// `Part One: 42`, for people
+    #[default]
+    Text,
+    /// An array of records with day, part, answer, type and elapsed time
+    Json,
+    /// The same records as JSON, one row each after a header
+    Csv,
+    /// Only the answers, one per line
+    Raw,
+}
+
+/// One solved part, as consumed by scripts. The answer stays a string so large values survive JSON
+/// parsers; `type` says how to read it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Solving the part, without parsing the input, in nanoseconds.
+    pub elapsed_ns: u64,
+}
+
+impl Record {
+    pub fn new(day: u8, part: Part, answer: &Answer, elapsed_ns: u64) -> Record {
+        Record {
+            day,
+            part: part.number(),
+            answer: answer.to_string(),
+            kind: answer.kind(),
+            elapsed_ns,
+        }
+    }
+}
+
+pub fn render(records: &[Record], format: Format) -> String {
+    let mut out: String = String::new();
+    match format {
+        Format::Text => {
+            for record in records {
+                let part: Part = Part::from_number(record.part).unwrap();
+                writeln!(out, "{}: {}", part, record.answer).unwrap();
+            }
+        }
+        Format::Json => {
+            out = serde_json::to_string_pretty(records).expect("records serialize to JSON");
+            out.push('\n');
+        }
+        Format::Csv => {
+            out.push_str("day,part,answer,type,elapsed_ns\n");
+            for record in records {
+                writeln!(
+                    out,
+                    "{},{},{},{},{}",
+                    record.day,
+                    record.part,
+                    csv_field(&record.answer),
+                    record.kind,
+                    record.elapsed_ns
+                )
+                .unwrap();
+            }
+        }
+        Format::Raw => {
+            for record in records {
+                writeln!(out, "{}", record.answer).unwrap();
+            }
+        }
+    }
+    out
+}
+
+// ? Quote fields that would otherwise break the row, doubling inner quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::new(6, Part::One, &Answer::Unsigned(41), 1200),
+            Record::new(6, Part::Two, &Answer::Text("a,\"b\"".to_string()), 3400),
+        ]
+    }
+
+    #[test]
+    fn renders_every_format() {
+        assert_eq!(render(&records(), Format::Text), "Part One: 41\nPart Two: a,\"b\"\n");
+        assert_eq!(render(&records(), Format::Raw), "41\na,\"b\"\n");
+        assert_eq!(
+            render(&records(), Format::Csv),
+            "day,part,answer,type,elapsed_ns\n6,1,41,unsigned,1200\n6,2,\"a,\"\"b\"\"\",text,3400\n"
+        );
+    }
+
+    #[test]
+    fn json_keeps_answers_as_strings() {
+        let json: serde_json::Value = serde_json::from_str(&render(&records(), Format::Json)).unwrap();
+        assert_eq!(
+            json[0],
+            serde_json::json!({"day": 6, "part": 1, "answer": "41", "type": "unsigned", "elapsed_ns": 1200})
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+use std::io::BufRead;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::error::ParseError;
+use crate::input::{self, InputProvider};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(n: u8) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "Part One"),
+            Part::Two => write!(f, "Part Two"),
+        }
+    }
+}
+
+/// The value produced by a part, erased so the runner can handle every day the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    Text(String),
+    Unsolved,
+    /// The part ran but could not produce an answer, e.g. because it overflowed.
+    Error(String),
+}
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "unsolved"),
+            Answer::Error(message) => write!(f, "error: {}", message),
+        }
+    }
+}
+impl Answer {
+    /// Read back a printed answer, as a number whenever it is one.
+    pub fn parse(text: &str) -> Answer {
+        if let Ok(n) = text.parse::<u128>() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = text.parse::<i128>() {
+            Answer::Signed(n)
+        } else {
+            Answer::Text(text.to_string())
+        }
+    }
+
+    /// What kind of value this is: `unsigned`, `signed`, `text`, `unsolved` or `error`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Signed(_) => "signed",
+            Answer::Text(_) => "text",
+            Answer::Unsolved => "unsolved",
+            Answer::Error(_) => "error",
+        }
+    }
+}
+macro_rules! impl_answer_from {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Answer {
+                Answer::$variant(value as $target)
+            }
+        })*
+    };
+}
+impl_answer_from!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+impl_answer_from!(Signed, i128, i8, i16, i32, i64, i128, isize);
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
+
+impl<T: Into<Answer>, E: fmt::Display> From<Result<T, E>> for Answer {
+    fn from(result: Result<T, E>) -> Answer {
+        match result {
+            Ok(value) => value.into(),
+            Err(err) => Answer::Error(err.to_string()),
+        }
+    }
+}
+
+/// Marker for a part that has not been solved yet.
+#[derive(Debug, Clone, Copy)]
+pub struct Unsolved;
+impl From<Unsolved> for Answer {
+    fn from(_: Unsolved) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+/// A day of the calendar: parse the puzzle input once, then solve each part from it.
+pub trait Solution {
+    const DAY: u8;
+    type Input;
+    type PartOne: Into<Answer>;
+    type PartTwo: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_one(input: &Self::Input) -> Self::PartOne;
+    fn part_two(input: &Self::Input) -> Self::PartTwo;
+
+    /// Parse straight from a reader. Reads everything into a `String` unless a day streams its input.
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, ParseError> {
+        let mut input: String = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|err| ParseError::new(1, 1, "", format!("cannot read the input: {}", err)))?;
+        Self::parse(&input)
+    }
+
+    fn solve(input: &str, part: Part) -> Result<Answer, ParseError> {
+        let parsed: Self::Input = Self::parse(input)?;
+        Ok(Self::answer(&parsed, part))
+    }
+
+    /// Parse `reader` once, then answer each of `parts` with how long it took, parsing excluded the
+    /// way `bench` times it.
+    fn solve_parts(reader: &mut dyn BufRead, parts: &[Part]) -> Result<Vec<(Part, Answer, Duration)>, ParseError> {
+        let parsed: Self::Input = Self::parse_reader(reader)?;
+        let timed = parts.iter().map(|&part| {
+            let start: Instant = Instant::now();
+            let answer: Answer = Self::answer(&parsed, part);
+            (part, answer, start.elapsed())
+        });
+        Ok(timed.collect())
+    }
+
+    fn answer(parsed: &Self::Input, part: Part) -> Answer {
+        match part {
+            Part::One => Self::part_one(parsed).into(),
+            Part::Two => Self::part_two(parsed).into(),
+        }
+    }
+}
+
+/// Entry point shared by the `day_N` binaries: solve both parts of `S` on its cached input.
+pub fn run<S: Solution>() -> ExitCode {
+    let provider: Box<dyn InputProvider> = input::default_provider();
+    let data: String = match provider.input(S::DAY) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let input: S::Input = match S::parse(&data) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err.with_file(provider.path(S::DAY)));
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut failed: bool = false;
+    for (part, answer) in [
+        (Part::One, S::part_one(&input).into()),
+        (Part::Two, S::part_two(&input).into()),
+    ] {
+        match answer {
+            Answer::Unsolved => (),
+            Answer::Error(message) => {
+                eprintln!("error: {}: {}", part, message);
+                failed = true;
+            }
+            answer => println!("{}: {}", part, answer),
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,392 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+
+use crate::answers::AnswerDbError;
+use crate::input::{self, YEAR};
+use crate::solution::{Answer, Part};
+
+static WAIT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+
+/// What the puzzle site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying in which direction.
+    Incorrect,
+    /// Answered too recently, try again after the duration.
+    Wait(Duration),
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Parse the HTML page answering a submission, `None` when it says none of the known things.
+    pub fn from_response(page: &str) -> Option<Outcome> {
+        if page.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Some(Outcome::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Outcome::TooLow)
+            } else {
+                Some(Outcome::Incorrect)
+            }
+        } else if page.contains("You gave an answer too recently") {
+            // ? One minute is the shortest lockout the site hands out, also used when the wait can't be read
+            let wait: u64 = WAIT_RE
+                .captures(page)
+                .and_then(|caps| {
+                    let minutes: u64 = match caps.get(1) {
+                        Some(minutes) => minutes.as_str().parse().ok()?,
+                        None => 0,
+                    };
+                    let seconds: u64 = caps[2].parse().ok()?;
+                    minutes.checked_mul(60)?.checked_add(seconds)
+                })
+                .unwrap_or(60);
+            Some(Outcome::Wait(Duration::from_secs(wait)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    fn hint(self) -> Option<&'static str> {
+        match self {
+            Outcome::TooHigh => Some("too high"),
+            Outcome::TooLow => Some("too low"),
+            Outcome::Incorrect => Some("incorrect"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Incorrect => write!(f, "wrong"),
+            Outcome::Wait(wait) => write!(f, "answered too recently, wait {}s", wait.as_secs()),
+            Outcome::AlreadySolved => write!(f, "this part is already solved"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Http(String),
+    Unrecognized(String),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitError::Http(message) => write!(f, "submission failed: {}", message),
+            SubmitError::Unrecognized(page) => write!(f, "unrecognized response: {}", page),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// How long the site refuses any answer after a wrong one, unless it says otherwise.
+pub const WRONG_ANSWER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Posts answers to the puzzle site with the account's session cookie.
+pub struct Submitter {
+    session: String,
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl Submitter {
+    pub fn new(session: impl Into<String>) -> Submitter {
+        Submitter {
+            session: session.into(),
+            base_url: input::base_url(),
+            agent: input::agent(),
+        }
+    }
+
+    /// Talk to another server than the puzzle site, e.g. a local stand-in during tests.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Submitter {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn submit(&self, day: u8, part: Part, answer: &Answer) -> Result<Outcome, SubmitError> {
+        let url: String = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let level: String = part.number().to_string();
+        let answer: String = answer.to_string();
+        let page: String = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level), ("answer", &answer)])
+            .map_err(|err| match err {
+                ureq::Error::Status(code, _) => SubmitError::Http(format!("{} answered {}", url, code)),
+                ureq::Error::Transport(err) => SubmitError::Http(err.to_string()),
+            })?
+            .into_string()
+            .map_err(|err| SubmitError::Http(err.to_string()))?;
+        Outcome::from_response(&page).ok_or_else(|| {
+            // ? The message sits in the page's <article>, fall back to its start otherwise
+            let text: &str = page
+                .split_once("<article>")
+                .and_then(|(_, rest)| rest.split_once("</article>"))
+                .map_or(page.as_str(), |(article, _)| article);
+            SubmitError::Unrecognized(text.chars().take(200).collect())
+        })
+    }
+}
+
+/// Answers the puzzle site rejected, stored in `wrong_answers.toml` as one table per day and part
+/// mapping each answer to the hint it got, after the unix time until which the site refuses answers:
+///
+/// ```toml
+/// blocked_until = 1733500000
+///
+/// [6.2]
+/// 1600 = "too high"
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WrongAnswers {
+    answers: BTreeMap<(u8, Part), BTreeMap<String, String>>,
+    blocked_until: Option<u64>,
+}
+
+impl WrongAnswers {
+    /// `wrong_answers.toml` at the root of the workspace.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().join("wrong_answers.toml")
+    }
+
+    /// Read the list at `path`, which is empty while the file doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<WrongAnswers, AnswerDbError> {
+        let path: &Path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(data) => WrongAnswers::parse(&data).map_err(|message| AnswerDbError::Invalid(path.to_path_buf(), message)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(WrongAnswers::default()),
+            Err(err) => Err(AnswerDbError::Io(path.to_path_buf(), err)),
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<WrongAnswers, String> {
+        let table: toml::Table = data.parse().map_err(|err: toml::de::Error| err.message().to_string())?;
+        let mut wrong: WrongAnswers = WrongAnswers::default();
+        for (day, parts) in table {
+            if day == "blocked_until" {
+                let toml::Value::Integer(until) = parts else {
+                    return Err("blocked_until should be a unix time".to_string());
+                };
+                wrong.blocked_until = Some(u64::try_from(until).map_err(|_| "blocked_until should be a unix time".to_string())?);
+                continue;
+            }
+            let day_number: u8 = day.parse().map_err(|_| format!("`{}` is not a day", day))?;
+            let toml::Value::Table(parts) = parts else {
+                return Err(format!("day {} should be a table of parts", day));
+            };
+            for (part, answers) in parts {
+                let part: Part = part
+                    .parse()
+                    .ok()
+                    .and_then(Part::from_number)
+                    .ok_or_else(|| format!("`{}` is not a part of day {}", part, day))?;
+                let toml::Value::Table(answers) = answers else {
+                    return Err(format!("day {} {} should be a table of answers", day, part));
+                };
+                for (answer, hint) in answers {
+                    let toml::Value::String(hint) = hint else {
+                        return Err(format!("the hint for `{}` on day {} {} should be a string", answer, day, part));
+                    };
+                    wrong.answers.entry((day_number, part)).or_default().insert(answer, hint);
+                }
+            }
+        }
+        Ok(wrong)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AnswerDbError> {
+        let path: &Path = path.as_ref();
+        std::fs::write(path, self.to_string()).map_err(|err| AnswerDbError::Io(path.to_path_buf(), err))
+    }
+
+    /// Remember a rejected answer, if `outcome` is a rejection.
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer, outcome: Outcome) {
+        if let Some(hint) = outcome.hint() {
+            self.answers.entry((day, part)).or_default().insert(answer.to_string(), hint.to_string());
+        }
+    }
+
+    /// Remember how long the site refuses answers after `outcome`, as of `now`.
+    pub fn lock_out(&mut self, outcome: Outcome, now: SystemTime) {
+        let wait: Duration = match outcome {
+            Outcome::Wait(wait) => wait,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect => WRONG_ANSWER_COOLDOWN,
+            Outcome::Correct | Outcome::AlreadySolved => return,
+        };
+        // ? Capped to what a TOML integer holds, so the file still loads
+        let until: u64 = unix_secs(now).saturating_add(wait.as_secs()).min(i64::MAX as u64);
+        self.blocked_until = Some(self.blocked_until.map_or(until, |blocked| blocked.max(until)));
+    }
+
+    /// How long until the site takes answers again, `None` once it does.
+    pub fn lockout(&self, now: SystemTime) -> Option<Duration> {
+        let remaining: u64 = self.blocked_until?.checked_sub(unix_secs(now))?;
+        (remaining > 0).then(|| Duration::from_secs(remaining))
+    }
+
+    /// Why `answer` is known to be wrong without asking the site again, if it is.
+    pub fn refusal(&self, day: u8, part: Part, answer: &Answer) -> Option<String> {
+        let rejected: &BTreeMap<String, String> = self.answers.get(&(day, part))?;
+        let answer: String = answer.to_string();
+        if let Some(hint) = rejected.get(&answer) {
+            return Some(format!("{} was already rejected as {}", answer, hint));
+        }
+        // ? Anything past a known bound is wrong too
+        let value: i128 = answer.parse().ok()?;
+        for (wrong, hint) in rejected {
+            let Ok(bound) = wrong.parse::<i128>() else {
+                continue;
+            };
+            match hint.as_str() {
+                "too high" if value >= bound => return Some(format!("{} was rejected as too high", bound)),
+                "too low" if value <= bound => return Some(format!("{} was rejected as too low", bound)),
+                _ => (),
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for WrongAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Answers the puzzle site rejected: day.part -> answer -> hint, checked by `aoc submit`.")?;
+        if let Some(until) = self.blocked_until {
+            writeln!(f, "blocked_until = {}", until)?;
+        }
+        for ((day, part), answers) in &self.answers {
+            writeln!(f, "\n[{}.{}]", day, part.number())?;
+            for (answer, hint) in answers {
+                writeln!(f, "{} = {}", toml::Value::String(answer.clone()), toml::Value::String(hint.clone()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::stand_in_server;
+
+    #[test]
+    fn parses_every_response() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        assert_eq!(Outcome::from_response(&page("That's the right answer! You are one gold star closer.")), Some(Outcome::Correct));
+        assert_eq!(
+            Outcome::from_response(&page("That's not the right answer; your answer is too high. Please wait one minute.")),
+            Some(Outcome::TooHigh)
+        );
+        assert_eq!(
+            Outcome::from_response(&page("That's not the right answer; your answer is too low.")),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(Outcome::from_response(&page("That's not the right answer.")), Some(Outcome::Incorrect));
+        assert_eq!(
+            Outcome::from_response(&page("You gave an answer too recently. You have 1m 5s left to wait.")),
+            Some(Outcome::Wait(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Outcome::from_response(&page("You gave an answer too recently. You have 42s left to wait.")),
+            Some(Outcome::Wait(Duration::from_secs(42)))
+        );
+        assert_eq!(
+            Outcome::from_response(&page("You gave an answer too recently. You have 99999999999999999999s left to wait.")),
+            Some(Outcome::Wait(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            Outcome::from_response(&page("You gave an answer too recently. You have 307445734561825861m 0s left to wait.")),
+            Some(Outcome::Wait(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            Outcome::from_response(&page("You don't seem to be solving the right level. Did you already complete it?")),
+            Some(Outcome::AlreadySolved)
+        );
+        assert_eq!(Outcome::from_response(&page("Something else entirely.")), None);
+    }
+
+    #[test]
+    fn posts_level_and_answer_with_the_session() {
+        let (url, requests) = stand_in_server("200 OK", "<article><p>That's the right answer!</p></article>");
+        let submitter: Submitter = Submitter::new("secret").with_base_url(url);
+        assert_eq!(submitter.submit(6, Part::Two, &Answer::Unsigned(1523)).unwrap(), Outcome::Correct);
+
+        let requests: Vec<String> = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with(&format!("POST /{}/day/6/answer ", YEAR)));
+        assert!(requests[0].contains("session=secret"));
+        assert!(requests[0].ends_with("level=2&answer=1523"));
+    }
+
+    #[test]
+    fn reports_unrecognized_pages() {
+        let (url, _) = stand_in_server("200 OK", "<article><p>Puzzle not unlocked yet.</p></article>");
+        let submitter: Submitter = Submitter::new("secret").with_base_url(url);
+        match submitter.submit(25, Part::One, &Answer::Unsigned(1)) {
+            Err(SubmitError::Unrecognized(text)) => assert_eq!(text, "<p>Puzzle not unlocked yet.</p>"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers_and_bounds() {
+        let mut wrong: WrongAnswers = WrongAnswers::default();
+        wrong.record(6, Part::Two, &Answer::Unsigned(1600), Outcome::TooHigh);
+        wrong.record(6, Part::Two, &Answer::Unsigned(1400), Outcome::TooLow);
+        wrong.record(6, Part::Two, &Answer::Unsigned(1500), Outcome::Incorrect);
+        wrong.record(6, Part::Two, &Answer::Unsigned(1523), Outcome::Correct);
+
+        let wrong: WrongAnswers = WrongAnswers::parse(&wrong.to_string()).unwrap();
+        assert!(wrong.refusal(6, Part::Two, &Answer::Unsigned(1500)).is_some());
+        assert!(wrong.refusal(6, Part::Two, &Answer::Unsigned(1700)).is_some());
+        assert!(wrong.refusal(6, Part::Two, &Answer::Unsigned(1300)).is_some());
+        assert_eq!(wrong.refusal(6, Part::Two, &Answer::Unsigned(1523)), None);
+        assert_eq!(wrong.refusal(6, Part::One, &Answer::Unsigned(1500)), None);
+    }
+
+    #[test]
+    fn keeps_the_lockout_until_it_expires() {
+        let now: SystemTime = UNIX_EPOCH + Duration::from_secs(1_733_500_000);
+        let mut wrong: WrongAnswers = WrongAnswers::default();
+        assert_eq!(wrong.lockout(now), None);
+        wrong.lock_out(Outcome::Correct, now);
+        assert_eq!(wrong.lockout(now), None);
+
+        wrong.lock_out(Outcome::Wait(Duration::from_secs(300)), now);
+        wrong.lock_out(Outcome::TooLow, now);
+        let wrong: WrongAnswers = WrongAnswers::parse(&wrong.to_string()).unwrap();
+        assert_eq!(wrong.lockout(now), Some(Duration::from_secs(300)));
+        assert_eq!(wrong.lockout(now + Duration::from_secs(240)), Some(Duration::from_secs(60)));
+        assert_eq!(wrong.lockout(now + Duration::from_secs(300)), None);
+        assert!(WrongAnswers::parse("blocked_until = -1").is_err());
+        let mut forever: WrongAnswers = WrongAnswers::default();
+        forever.lock_out(Outcome::Wait(Duration::from_secs(u64::MAX)), now);
+        assert!(WrongAnswers::parse(&forever.to_string()).unwrap().lockout(now).is_some());
+    }
+}
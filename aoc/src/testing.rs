@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+use crate::days;
+use crate::solution::{Answer, Part};
+
+/// The `examples` directory at the root of the workspace, holding `dayN_partM_K.txt` puzzle examples.
+pub fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().join("examples")
+}
+
+/// Solve `part` of `day` on `examples/<file>`, panicking with the reason when it can't.
+pub fn solve_example(day: u8, part: u8, file: &str) -> Answer {
+    let solver: days::Solver = days::solver(day).unwrap_or_else(|| panic!("day {} has no registered solution", day));
+    let part: Part = Part::from_number(part).unwrap_or_else(|| panic!("day {} has no part {}", day, part));
+    let path: PathBuf = examples_dir().join(file);
+    let data: String = std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("cannot read {}: {}", path.display(), err));
+    solver(&data, part).unwrap_or_else(|err| panic!("{}", err.with_file(&path)))
+}
+
+/// Declare a test solving one part of a day on an example file and comparing the printed answer:
+///
+/// ```ignore
+/// aoc_example_test!(day6_part2: day = 6, part = 2, file = "day6_part1_1.txt", expect = 6);
+/// ```
+#[macro_export]
+macro_rules! aoc_example_test {
+    ($name:ident: day = $day:expr, part = $part:expr, file = $file:expr, expect = $expect:expr $(,)?) => {
+        #[test]
+        fn $name() {
+            let answer: $crate::Answer = $crate::testing::solve_example($day, $part, $file);
+            assert_eq!(answer.to_string(), $expect.to_string(), "day {} part {} on {}", $day, $part, $file);
+        }
+    };
+}
+
+/// A seeded xorshift generator, so randomized tests draw the same values on every run.
+#[cfg(test)]
+pub(crate) struct Rng(u64);
+
+#[cfg(test)]
+impl Rng {
+    /// `seed` must not be zero, which xorshift never leaves.
+    pub(crate) fn new(seed: u64) -> Rng {
+        assert_ne!(seed, 0, "xorshift needs a non-zero seed");
+        Rng(seed)
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `0..bound`.
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+/// Serve `status` and `body` to every request on a local port, recording each request it receives
+/// (head and body) so tests can stand in for the puzzle site.
+#[cfg(test)]
+pub(crate) fn stand_in_server(
+    status: &'static str,
+    body: &'static str,
+) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url: String = format!("http://{}", listener.local_addr().unwrap());
+    let requests: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+    let seen: Arc<Mutex<Vec<String>>> = Arc::clone(&requests);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request: String = String::new();
+            let mut reader = BufReader::new(&stream);
+            let mut content_length: usize = 0;
+            loop {
+                let mut line: String = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                request.push_str(&line);
+            }
+            let mut body_bytes: Vec<u8> = vec![0; content_length];
+            reader.read_exact(&mut body_bytes).unwrap();
+            request.push_str("\r\n");
+            request.push_str(&String::from_utf8_lossy(&body_bytes));
+            seen.lock().unwrap().push(request);
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (url, requests)
+}
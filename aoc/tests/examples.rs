@@ -0,0 +1,58 @@
+//! The official examples of each puzzle, see `examples/` at the root of the workspace.
+//! Parts without an example answer in the puzzle text are pinned to what the solution gives today,
+//! and the unsolved second parts of days 12 and 15 are left out. The tree of day 14 only shows up
+//! in real inputs, so that part is left to `answers.toml`.
+
+use aoc::aoc_example_test;
+use aoc::days::day_14::{self, Room};
+use aoc::testing::examples_dir;
+
+aoc_example_test!(day1_part1: day = 1, part = 1, file = "day1_part1_1.txt", expect = 11);
+aoc_example_test!(day1_part2: day = 1, part = 2, file = "day1_part1_1.txt", expect = 31);
+
+aoc_example_test!(day2_part1: day = 2, part = 1, file = "day2_part1_1.txt", expect = 2);
+aoc_example_test!(day2_part2: day = 2, part = 2, file = "day2_part1_1.txt", expect = 4);
+
+aoc_example_test!(day3_part1: day = 3, part = 1, file = "day3_part1_1.txt", expect = 161);
+aoc_example_test!(day3_part2: day = 3, part = 2, file = "day3_part2_1.txt", expect = 48);
+
+aoc_example_test!(day4_part1: day = 4, part = 1, file = "day4_part1_1.txt", expect = 18);
+aoc_example_test!(day4_part2: day = 4, part = 2, file = "day4_part1_1.txt", expect = 9);
+
+aoc_example_test!(day5_part1: day = 5, part = 1, file = "day5_part1_1.txt", expect = 143);
+aoc_example_test!(day5_part2: day = 5, part = 2, file = "day5_part1_1.txt", expect = 123);
+
+aoc_example_test!(day6_part1: day = 6, part = 1, file = "day6_part1_1.txt", expect = 41);
+aoc_example_test!(day6_part2: day = 6, part = 2, file = "day6_part1_1.txt", expect = 6);
+
+aoc_example_test!(day7_part1: day = 7, part = 1, file = "day7_part1_1.txt", expect = 3749);
+aoc_example_test!(day7_part2: day = 7, part = 2, file = "day7_part1_1.txt", expect = 11387);
+
+aoc_example_test!(day8_part1: day = 8, part = 1, file = "day8_part1_1.txt", expect = 14);
+aoc_example_test!(day8_part2: day = 8, part = 2, file = "day8_part1_1.txt", expect = 34);
+aoc_example_test!(day8_part2_small: day = 8, part = 2, file = "day8_part2_1.txt", expect = 9);
+
+aoc_example_test!(day9_part1: day = 9, part = 1, file = "day9_part1_1.txt", expect = 1928);
+aoc_example_test!(day9_part2: day = 9, part = 2, file = "day9_part1_1.txt", expect = 2858);
+
+aoc_example_test!(day10_part1: day = 10, part = 1, file = "day10_part1_1.txt", expect = 36);
+aoc_example_test!(day10_part2: day = 10, part = 2, file = "day10_part1_1.txt", expect = 81);
+
+aoc_example_test!(day11_part1: day = 11, part = 1, file = "day11_part1_1.txt", expect = 55312);
+aoc_example_test!(day11_part2: day = 11, part = 2, file = "day11_part1_1.txt", expect = 65601038650482_u64);
+
+aoc_example_test!(day12_part1: day = 12, part = 1, file = "day12_part1_1.txt", expect = 140);
+aoc_example_test!(day12_part1_nested: day = 12, part = 1, file = "day12_part1_2.txt", expect = 772);
+
+aoc_example_test!(day13_part1: day = 13, part = 1, file = "day13_part1_1.txt", expect = 480);
+aoc_example_test!(day13_part2: day = 13, part = 2, file = "day13_part1_1.txt", expect = 875318608908_u64);
+
+// ? The example runs in a smaller room than the one the registered solution assumes
+#[test]
+fn day14_part1() {
+    let data: String = std::fs::read_to_string(examples_dir().join("day14_part1_1.txt")).unwrap();
+    let room: Room = day_14::load_room(&data, day_14::EXAMPLE_SIZE).unwrap();
+    assert_eq!(day_14::part_one(&room.robots, room.size), 12);
+}
+
+aoc_example_test!(day15_part1: day = 15, part = 1, file = "day15_part1_1.txt", expect = 2028);